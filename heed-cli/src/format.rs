@@ -0,0 +1,141 @@
+use std::convert::TryInto;
+use std::fmt::Write;
+use std::ops::Bound;
+use std::str::FromStr;
+
+/// A range of keys, parsed from the command line.
+pub type KeyRange = (Bound<Vec<u8>>, Bound<Vec<u8>>);
+
+/// How the keys and values are read from the command line and printed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Lowercase hexadecimal digits.
+    Hex,
+    /// An utf-8 string, printed with the special characters escaped.
+    Utf8,
+    /// A big-endian `u16`.
+    U16,
+    /// A big-endian `u32`.
+    U32,
+    /// A big-endian `u64`.
+    U64,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "hex" => Ok(Format::Hex),
+            "utf8" | "utf-8" => Ok(Format::Utf8),
+            "u16" => Ok(Format::U16),
+            "u32" => Ok(Format::U32),
+            "u64" => Ok(Format::U64),
+            _ => Err(format!("unknown format {:?}, expected hex, utf8, u16, u32 or u64", s)),
+        }
+    }
+}
+
+impl Format {
+    /// Parses the bytes written in this format on the command line.
+    pub fn parse(self, s: &str) -> Result<Vec<u8>, String> {
+        let invalid_int = |e| format!("invalid integer {:?}: {}", s, e);
+        match self {
+            Format::Hex => parse_hex(s).ok_or_else(|| format!("invalid hexadecimal {:?}", s)),
+            Format::Utf8 => Ok(s.as_bytes().to_vec()),
+            Format::U16 => s.parse::<u16>().map(|n| n.to_be_bytes().to_vec()).map_err(invalid_int),
+            Format::U32 => s.parse::<u32>().map(|n| n.to_be_bytes().to_vec()).map_err(invalid_int),
+            Format::U64 => s.parse::<u64>().map(|n| n.to_be_bytes().to_vec()).map_err(invalid_int),
+        }
+    }
+
+    /// Renders the bytes in this format, the bytes that cannot be rendered
+    /// in this format are rendered in hexadecimal, prefixed by `0x`.
+    pub fn display(self, bytes: &[u8]) -> String {
+        let rendered = match self {
+            Format::Hex => return to_hex(bytes),
+            Format::Utf8 => std::str::from_utf8(bytes).ok().map(|s| s.escape_debug().to_string()),
+            Format::U16 => bytes.try_into().ok().map(|b| u16::from_be_bytes(b).to_string()),
+            Format::U32 => bytes.try_into().ok().map(|b| u32::from_be_bytes(b).to_string()),
+            Format::U64 => bytes.try_into().ok().map(|b| u64::from_be_bytes(b).to_string()),
+        };
+
+        rendered.unwrap_or_else(|| format!("0x{}", to_hex(bytes)))
+    }
+
+    /// Parses a range of keys written as `start..end` or `start..=end`,
+    /// the start and the end being optional.
+    pub fn parse_range(self, s: &str) -> Result<KeyRange, String> {
+        let (start, end, inclusive) = match s.find("..=") {
+            Some(pos) => (&s[..pos], &s[pos + 3..], true),
+            None => match s.find("..") {
+                Some(pos) => (&s[..pos], &s[pos + 2..], false),
+                None => return Err(format!("invalid range {:?}, expected start..end", s)),
+            },
+        };
+
+        let start = match start {
+            "" => Bound::Unbounded,
+            start => Bound::Included(self.parse(start)?),
+        };
+        let end = match end {
+            "" if inclusive => return Err(format!("invalid range {:?}, the end is missing", s)),
+            "" => Bound::Unbounded,
+            end if inclusive => Bound::Included(self.parse(end)?),
+            end => Bound::Excluded(self.parse(end)?),
+        };
+
+        Ok((start, end))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return None;
+    }
+
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(Format::Hex.parse("00ff10").unwrap(), vec![0, 255, 16]);
+        assert_eq!(Format::Hex.parse("0x00ff").unwrap(), vec![0, 255]);
+        assert!(Format::Hex.parse("0f0").is_err());
+        assert_eq!(Format::U32.parse("258").unwrap(), vec![0, 0, 1, 2]);
+        assert!(Format::U16.parse("65536").is_err());
+
+        assert_eq!(Format::Hex.display(&[0, 255, 16]), "00ff10");
+        assert_eq!(Format::Utf8.display(b"hello\tworld"), "hello\\tworld");
+        assert_eq!(Format::Utf8.display(&[0xff]), "0xff");
+        assert_eq!(Format::U32.display(&[0, 0, 1, 2]), "258");
+        assert_eq!(Format::U64.display(&[0, 0, 1, 2]), "0x00000102");
+    }
+
+    #[test]
+    fn parse_ranges() {
+        use std::ops::Bound::*;
+
+        let range = Format::U16.parse_range("1..=2").unwrap();
+        assert_eq!(range, (Included(vec![0, 1]), Included(vec![0, 2])));
+        let range = Format::Utf8.parse_range("a..").unwrap();
+        assert_eq!(range, (Included(b"a".to_vec()), Unbounded));
+        let range = Format::Utf8.parse_range("..b").unwrap();
+        assert_eq!(range, (Unbounded, Excluded(b"b".to_vec())));
+        assert!(Format::Utf8.parse_range("a..=").is_err());
+        assert!(Format::Utf8.parse_range("ab").is_err());
+    }
+}
@@ -0,0 +1,401 @@
+//! A command-line tool to inspect and maintain heed environments.
+
+mod format;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use heed::types::ByteSlice;
+use heed::{CompactionOption, DumpFormat, EnvOpenOptions, ReadOnlyEnv, UntypedDatabase};
+
+use crate::format::Format;
+
+const USAGE: &str = "\
+Usage: heed-cli <command> <env-path> [options]
+
+Commands:
+    stat                  Prints the statistics of the environment, or of a database with --db
+    list-dbs              Lists the named databases stored in the unnamed database
+    get <key>             Prints the value associated with a key
+    scan                  Prints the entries, restricted with --prefix or --range
+    dump                  Writes databases to stdout in the mdb_dump format
+    load                  Loads databases from stdin in the mdb_dump format
+    copy <dest-path>      Copies the environment into another directory
+    readers               Lists the reader slots in use
+
+Options:
+    --db <name>           The database to use, the unnamed one by default,
+                          can be repeated with dump
+    --all                 Dumps all the databases
+    --key-format <fmt>    hex, utf8, u16, u32 or u64 (big-endian), hex by default
+    --value-format <fmt>  The format of the values, hex by default
+    --prefix <key>        Only scans the keys starting with this prefix
+    --range <range>       Only scans the keys in this range: start..end or start..=end,
+                          the start and the end are optional
+    --limit <n>           Scans at most n entries
+    --print               Dumps the printable characters as is instead of hexadecimal
+    --compact             Compacts the environment while copying it
+    --max-dbs <n>         The maximum number of named databases, 128 by default
+    --map-size <bytes>    The map size used to load databases
+";
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+type Entries<'txn> = Box<dyn Iterator<Item = heed::Result<(&'txn [u8], &'txn [u8])>> + 'txn>;
+
+/// The arguments given on the command line.
+#[derive(Debug, Default)]
+struct Args {
+    command: String,
+    path: PathBuf,
+    positionals: Vec<String>,
+    databases: Vec<String>,
+    all: bool,
+    key_format: Option<Format>,
+    value_format: Option<Format>,
+    prefix: Option<String>,
+    range: Option<String>,
+    limit: Option<usize>,
+    print: bool,
+    compact: bool,
+    max_dbs: Option<u32>,
+    map_size: Option<usize>,
+}
+
+impl Args {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args> {
+        let mut args = args.into_iter();
+        let mut parsed = Args::default();
+        let mut positionals = Vec::new();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next().ok_or_else(|| format!("the {} option requires a value", name))
+            };
+
+            match arg.as_str() {
+                "--db" => parsed.databases.push(value("--db")?),
+                "--all" => parsed.all = true,
+                "--key-format" => parsed.key_format = Some(value("--key-format")?.parse()?),
+                "--value-format" => parsed.value_format = Some(value("--value-format")?.parse()?),
+                "--prefix" => parsed.prefix = Some(value("--prefix")?),
+                "--range" => parsed.range = Some(value("--range")?),
+                "--limit" => parsed.limit = Some(value("--limit")?.parse()?),
+                "--print" => parsed.print = true,
+                "--compact" => parsed.compact = true,
+                "--max-dbs" => parsed.max_dbs = Some(value("--max-dbs")?.parse()?),
+                "--map-size" => parsed.map_size = Some(value("--map-size")?.parse()?),
+                option if option.starts_with("--") => {
+                    return Err(format!("unknown option {}", option).into())
+                }
+                _ => positionals.push(arg),
+            }
+        }
+
+        let mut positionals = positionals.into_iter();
+        parsed.command = positionals.next().ok_or("a command is required")?;
+        parsed.path = positionals.next().ok_or("the path of the environment is required")?.into();
+        parsed.positionals = positionals.collect();
+
+        if parsed.prefix.is_some() && parsed.range.is_some() {
+            return Err("the --prefix and --range options cannot be used together".into());
+        }
+        if parsed.databases.len() > 1 && parsed.command != "dump" {
+            return Err("only the dump command accepts several databases".into());
+        }
+
+        Ok(parsed)
+    }
+
+    fn database(&self) -> Option<&str> {
+        self.databases.first().map(String::as_str)
+    }
+
+    fn key_format(&self) -> Format {
+        self.key_format.unwrap_or(Format::Hex)
+    }
+
+    fn value_format(&self) -> Format {
+        self.value_format.unwrap_or(Format::Hex)
+    }
+
+    fn expect_positionals(&self, count: usize) -> Result<()> {
+        if self.positionals.len() != count {
+            let msg = format!("the {} command expects {} argument(s)", self.command, count);
+            return Err(msg.into());
+        }
+        Ok(())
+    }
+
+    fn env_options(&self) -> EnvOpenOptions {
+        let mut options = EnvOpenOptions::new();
+        options.max_dbs(self.max_dbs.unwrap_or(128));
+        if let Some(map_size) = self.map_size {
+            options.map_size(map_size);
+        }
+        options
+    }
+
+    fn open_read_only(&self) -> Result<ReadOnlyEnv> {
+        if !self.path.exists() {
+            return Err(format!("{} does not exist", self.path.display()).into());
+        }
+        Ok(self.env_options().open_read_only(&self.path)?)
+    }
+}
+
+fn open_database(env: &ReadOnlyEnv, name: Option<&str>) -> Result<UntypedDatabase> {
+    match env.open_database(name)? {
+        Some(db) => Ok(db),
+        None => Err(format!("the database {} does not exist", name.unwrap_or("<unnamed>")).into()),
+    }
+}
+
+/// Returns the names of the databases stored in the unnamed database.
+fn database_names(env: &ReadOnlyEnv) -> Result<Vec<String>> {
+    Ok(env.read(|rtxn| env.database_names(rtxn))?)
+}
+
+fn stat<W: Write>(args: &Args, mut out: W) -> Result<()> {
+    args.expect_positionals(0)?;
+    let env = args.open_read_only()?;
+
+    let stat = match args.database() {
+        Some(name) => {
+            let db = open_database(&env, Some(name))?;
+            db.stat(&env.read_txn()?)?
+        }
+        None => {
+            let info = env.info()?;
+            writeln!(out, "map size: {}", info.map_size)?;
+            writeln!(out, "last page number: {}", info.last_page_number)?;
+            writeln!(out, "last transaction id: {}", info.last_txn_id)?;
+            writeln!(out, "max readers: {}", info.max_readers)?;
+            writeln!(out, "readers in use: {}", info.num_readers)?;
+            env.stat()?
+        }
+    };
+
+    writeln!(out, "page size: {}", stat.page_size)?;
+    writeln!(out, "depth: {}", stat.depth)?;
+    writeln!(out, "branch pages: {}", stat.branch_pages)?;
+    writeln!(out, "leaf pages: {}", stat.leaf_pages)?;
+    writeln!(out, "overflow pages: {}", stat.overflow_pages)?;
+    writeln!(out, "entries: {}", stat.entries)?;
+    Ok(())
+}
+
+fn list_dbs<W: Write>(args: &Args, mut out: W) -> Result<()> {
+    args.expect_positionals(0)?;
+    let env = args.open_read_only()?;
+    let names = database_names(&env)?;
+    let databases = names.iter().map(|name| open_database(&env, Some(name))).collect::<Result<Vec<_>>>()?;
+
+    let rtxn = env.read_txn()?;
+    for (name, db) in names.iter().zip(databases) {
+        writeln!(out, "{}\t{}", name, db.len(&rtxn)?)?;
+    }
+
+    Ok(())
+}
+
+fn get<W: Write>(args: &Args, mut out: W) -> Result<()> {
+    args.expect_positionals(1)?;
+    let env = args.open_read_only()?;
+    let db = open_database(&env, args.database())?;
+    let rtxn = env.read_txn()?;
+
+    let key = args.key_format().parse(&args.positionals[0])?;
+    match db.remap_types::<ByteSlice, ByteSlice>().get(&rtxn, &&key[..])? {
+        Some(value) => writeln!(out, "{}", args.value_format().display(value))?,
+        None => return Err("the key does not exist".into()),
+    }
+
+    Ok(())
+}
+
+fn scan<W: Write>(args: &Args, mut out: W) -> Result<()> {
+    args.expect_positionals(0)?;
+    let env = args.open_read_only()?;
+    let db = open_database(&env, args.database())?.remap_types::<ByteSlice, ByteSlice>();
+    let rtxn = env.read_txn()?;
+
+    let prefix = args.prefix.as_deref().map(|p| args.key_format().parse(p)).transpose()?;
+    let range = args.range.as_deref().map(|r| args.key_format().parse_range(r)).transpose()?;
+
+    let iter: Entries = match (&prefix, &range) {
+        (Some(prefix), _) => Box::new(db.prefix_iter(&rtxn, &&prefix[..])?),
+        (None, Some((start, end))) => {
+            let bounds = (as_slice_bound(start), as_slice_bound(end));
+            Box::new(db.range(&rtxn, bounds)?)
+        }
+        (None, None) => Box::new(db.iter(&rtxn)?),
+    };
+
+    for result in iter.take(args.limit.unwrap_or(usize::MAX)) {
+        let (key, value) = result?;
+        let key = args.key_format().display(key);
+        writeln!(out, "{}\t{}", key, args.value_format().display(value))?;
+    }
+
+    Ok(())
+}
+
+fn as_slice_bound(bound: &Bound<Vec<u8>>) -> Bound<&[u8]> {
+    match bound {
+        Bound::Included(bytes) => Bound::Included(&bytes[..]),
+        Bound::Excluded(bytes) => Bound::Excluded(&bytes[..]),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn dump<W: Write>(args: &Args, out: W) -> Result<()> {
+    args.expect_positionals(0)?;
+    let env = args.open_read_only()?;
+
+    let names = if args.all { database_names(&env)? } else { args.databases.clone() };
+    let mut databases: Vec<_> = names.iter().map(|name| Some(name.as_str())).collect();
+    if args.all || databases.is_empty() {
+        databases.insert(0, None);
+    }
+
+    let format = if args.print { DumpFormat::Print } else { DumpFormat::ByteValue };
+    env.dump(out, &databases, format)?;
+    Ok(())
+}
+
+fn load<R: BufRead>(args: &Args, input: R) -> Result<()> {
+    args.expect_positionals(0)?;
+    fs::create_dir_all(&args.path)?;
+    let env = args.env_options().open(&args.path)?;
+    env.load(input)?;
+    Ok(())
+}
+
+fn copy(args: &Args) -> Result<()> {
+    args.expect_positionals(1)?;
+    let env = args.open_read_only()?;
+
+    let dest = Path::new(&args.positionals[0]);
+    fs::create_dir_all(dest)?;
+    let option = if args.compact { CompactionOption::Enabled } else { CompactionOption::Disabled };
+    env.copy_to_path(dest.join("data.mdb"), option)?;
+    Ok(())
+}
+
+fn readers<W: Write>(args: &Args, mut out: W) -> Result<()> {
+    args.expect_positionals(0)?;
+    let env = args.open_read_only()?;
+
+    writeln!(out, "pid\tthread\ttxn id")?;
+    for reader in env.readers()? {
+        let txn_id = reader.txn_id.map_or_else(|| "-".to_string(), |id| id.to_string());
+        writeln!(out, "{}\t{:x}\t{}", reader.pid, reader.thread, txn_id)?;
+    }
+
+    Ok(())
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+
+    let args = Args::parse(args)?;
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    match args.command.as_str() {
+        "stat" => stat(&args, &mut out)?,
+        "list-dbs" => list_dbs(&args, &mut out)?,
+        "get" => get(&args, &mut out)?,
+        "scan" => scan(&args, &mut out)?,
+        "dump" => dump(&args, &mut out)?,
+        "load" => load(&args, io::stdin().lock())?,
+        "copy" => copy(&args)?,
+        "readers" => readers(&args, &mut out)?,
+        command => return Err(format!("unknown command {}", command).into()),
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(std::env::args().skip(1).collect()) {
+        eprintln!("error: {}", e);
+        eprintln!("run heed-cli --help for the usage");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_arguments() {
+        let parsed = Args::parse(args(&["get", "env.mdb", "--db", "users", "42", "--key-format", "u32"]))
+            .unwrap();
+        assert_eq!(parsed.command, "get");
+        assert_eq!(parsed.path, Path::new("env.mdb"));
+        assert_eq!(parsed.positionals, vec!["42"]);
+        assert_eq!(parsed.database(), Some("users"));
+        assert_eq!(parsed.key_format(), Format::U32);
+        assert_eq!(parsed.value_format(), Format::Hex);
+
+        assert!(Args::parse(args(&["scan"])).is_err());
+        assert!(Args::parse(args(&["scan", "env.mdb", "--limit"])).is_err());
+        assert!(Args::parse(args(&["scan", "env.mdb", "--unknown"])).is_err());
+        assert!(Args::parse(args(&["scan", "env.mdb", "--prefix", "a", "--range", "a..b"])).is_err());
+        assert!(Args::parse(args(&["get", "env.mdb", "--db", "a", "--db", "b"])).is_err());
+    }
+
+    #[test]
+    fn scan_and_dump() {
+        let path = Path::new("../target").join("heed-cli-scan.mdb");
+        fs::create_dir_all(&path).unwrap();
+        let env = EnvOpenOptions::new().max_dbs(10).open(&path).unwrap();
+        let db: UntypedDatabase = env.create_database(Some("numbers")).unwrap();
+        let mut wtxn = env.write_txn().unwrap();
+        db.clear(&mut wtxn).unwrap();
+        for i in 0u32..10 {
+            db.put(&mut wtxn, &&i.to_be_bytes()[..], &&b"value"[..]).unwrap();
+        }
+        wtxn.commit().unwrap();
+
+        let path = path.to_str().unwrap();
+        let scan_args = ["scan", path, "--db", "numbers", "--key-format", "u32", "--value-format", "utf8"];
+
+        let mut out = Vec::new();
+        let mut range_args = args(&scan_args);
+        range_args.extend(args(&["--range", "3..=4"]));
+        scan(&Args::parse(range_args).unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "3\tvalue\n4\tvalue\n");
+
+        let mut out = Vec::new();
+        let mut limit_args = args(&scan_args);
+        limit_args.extend(args(&["--range", "8..", "--limit", "1"]));
+        scan(&Args::parse(limit_args).unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "8\tvalue\n");
+
+        let mut out = Vec::new();
+        list_dbs(&Args::parse(args(&["list-dbs", path])).unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "numbers\t10\n");
+
+        let mut out = Vec::new();
+        dump(&Args::parse(args(&["dump", path, "--all", "--print"])).unwrap(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("database=numbers\n"));
+        assert_eq!(out.matches("HEADER=END").count(), 2);
+    }
+}
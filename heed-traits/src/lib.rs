@@ -1,10 +1,31 @@
+use std::cmp::Ordering;
 use std::borrow::Cow;
 use std::error::Error;
 
 pub trait BytesEncode {
     type EItem: ?Sized;
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>>;
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>>;
+
+    /// Returns the exact size of the encoded item when it can be known without encoding it,
+    /// `Database::put_encoded_in_place` then encodes it with [`BytesEncode::bytes_encode_into`]
+    /// directly where it is stored.
+    fn encoded_size(_item: &Self::EItem) -> Option<usize> {
+        None
+    }
+
+    /// Encodes the item into the given buffer, of the size returned by [`BytesEncode::encoded_size`].
+    ///
+    /// The buffer is zeroed. When it fails while the item is written in place, with
+    /// `Database::put_encoded_in_place`, the write transaction must be aborted.
+    fn bytes_encode_into(item: &Self::EItem, buffer: &mut [u8]) -> Result<(), Box<dyn Error>> {
+        let bytes = Self::bytes_encode(item)?;
+        if bytes.len() != buffer.len() {
+            return Err(format!("encoded {} bytes instead of {}", bytes.len(), buffer.len()).into());
+        }
+        buffer.copy_from_slice(&bytes);
+        Ok(())
+    }
 }
 
 pub trait BytesDecode<'a> {
@@ -12,3 +33,32 @@ pub trait BytesDecode<'a> {
 
     fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, Box<dyn Error>>;
 }
+
+/// Defines how the keys (or duplicate data items) of a database are sorted.
+///
+/// The comparison must be a total order and stay the same for the whole life of
+/// the database, LMDB relies on it to find the entries it stored. It must not panic,
+/// a panic can't unwind through LMDB and aborts the process.
+///
+/// Prefix iteration is not supported on a database with a custom key comparator,
+/// the keys sharing a common bytes prefix are not necessarily sorted next to each other.
+pub trait Comparator {
+    fn compare(a: &[u8], b: &[u8]) -> Ordering;
+}
+
+/// The default LMDB comparator, compares the bytes in lexicographic order.
+pub enum DefaultComparator {}
+
+impl Comparator for DefaultComparator {
+    fn compare(a: &[u8], b: &[u8]) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Identifies the encoding of a codec in the fingerprint that heed can store with a database.
+///
+/// The id must be changed each time the encoding changes without the type name of the codec
+/// changing, for example when the fields of a serialized struct are modified.
+pub trait CodecSchema {
+    const SCHEMA_ID: u32;
+}
@@ -24,7 +24,7 @@ pub struct CowSlice<'a, T>(std::marker::PhantomData<&'a T>);
 impl<'a, T: Pod> BytesEncode for CowSlice<'a, T> {
     type EItem = &'a [T];
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         try_cast_slice(item).map(Cow::Borrowed).map_err(Into::into)
     }
 }
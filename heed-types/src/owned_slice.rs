@@ -24,7 +24,7 @@ pub struct OwnedSlice<'a, T>(std::marker::PhantomData<&'a T>);
 impl<'a, T: Pod> BytesEncode for OwnedSlice<'a, T> {
     type EItem = &'a [T];
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         CowSlice::bytes_encode(item)
     }
 }
@@ -30,7 +30,7 @@ pub struct OwnedType<T>(std::marker::PhantomData<T>);
 impl<T: Pod> BytesEncode for OwnedType<T> {
     type EItem = T;
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         CowType::bytes_encode(item)
     }
 }
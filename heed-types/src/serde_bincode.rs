@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::convert::TryInto;
 use std::error::Error;
 
 use heed_traits::{BytesDecode, BytesEncode};
@@ -15,9 +16,22 @@ where
 {
     type EItem = T;
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         bincode::serialize(item).map(Cow::Owned).map_err(Into::into)
     }
+
+    fn encoded_size(item: &Self::EItem) -> Option<usize> {
+        bincode::serialized_size(item).ok().and_then(|size| size.try_into().ok())
+    }
+
+    fn bytes_encode_into(item: &Self::EItem, mut buffer: &mut [u8]) -> Result<(), Box<dyn Error>> {
+        bincode::serialize_into(&mut buffer, item)?;
+        if buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{} bytes were not written", buffer.len()).into())
+        }
+    }
 }
 
 impl<'a, T: 'a> BytesDecode<'a> for SerdeBincode<T>
@@ -34,3 +48,23 @@ where
 unsafe impl<T> Send for SerdeBincode<T> {}
 
 unsafe impl<T> Sync for SerdeBincode<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_into() {
+        let item = (42u32, String::from("hello"));
+        let size = SerdeBincode::<(u32, String)>::encoded_size(&item).unwrap();
+        let bytes = SerdeBincode::<(u32, String)>::bytes_encode(&item).unwrap();
+        assert_eq!(size, bytes.len());
+
+        let mut buffer = vec![0; size];
+        SerdeBincode::<(u32, String)>::bytes_encode_into(&item, &mut buffer).unwrap();
+        assert_eq!(buffer, bytes.as_ref());
+
+        let mut buffer = vec![0; size + 1];
+        assert!(SerdeBincode::<(u32, String)>::bytes_encode_into(&item, &mut buffer).is_err());
+    }
+}
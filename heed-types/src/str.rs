@@ -13,7 +13,7 @@ pub struct Str<'a> {
 impl<'a> BytesEncode for Str<'a> {
     type EItem = &'a str;
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         try_cast_slice(item.as_bytes()).map(Cow::Borrowed).map_err(Into::into)
     }
 }
@@ -23,7 +23,7 @@ pub struct UnalignedType<T>(std::marker::PhantomData<T>);
 impl<T: Pod> BytesEncode for UnalignedType<T> {
     type EItem = T;
 
-    fn bytes_encode(item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         Ok(Cow::Borrowed(bytes_of(item)))
     }
 }
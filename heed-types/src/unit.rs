@@ -10,7 +10,7 @@ pub struct Unit;
 impl BytesEncode for Unit {
     type EItem = ();
 
-    fn bytes_encode(_item: &Self::EItem) -> Result<Cow<[u8]>, Box<dyn Error>> {
+    fn bytes_encode(_item: &Self::EItem) -> Result<Cow<'_, [u8]>, Box<dyn Error>> {
         Ok(Cow::Borrowed(&[]))
     }
 }
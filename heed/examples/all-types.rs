@@ -25,7 +25,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<OwnedType<[i32; 2]>, Str> = env.create_database(Some("kikou"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &[2, 3], &"what's up?")?;
+    db.put(&mut wtxn, &[2, 3], &"what's up?")?;
     let ret: Option<&str> = db.get(&wtxn, &[2, 3])?;
 
     println!("{:?}", ret);
@@ -35,7 +35,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<Str, ByteSlice> = env.create_database(Some("kiki"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &"hello", &&[2, 3][..])?;
+    db.put(&mut wtxn, &"hello", &&[2, 3][..])?;
     let ret: Option<&[u8]> = db.get(&wtxn, &"hello")?;
 
     println!("{:?}", ret);
@@ -95,7 +95,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<Str, Unit> = env.create_database(Some("ignored-data"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &"hello", &())?;
+    db.put(&mut wtxn, &"hello", &())?;
     let ret: Option<()> = db.get(&wtxn, &"hello")?;
 
     println!("{:?}", ret);
@@ -125,10 +125,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<OwnedType<BEI64>, Unit> = env.create_database(Some("big-endian-iter"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &BEI64::new(0), &())?;
-    let _ret = db.put(&mut wtxn, &BEI64::new(68), &())?;
-    let _ret = db.put(&mut wtxn, &BEI64::new(35), &())?;
-    let _ret = db.put(&mut wtxn, &BEI64::new(42), &())?;
+    db.put(&mut wtxn, &BEI64::new(0), &())?;
+    db.put(&mut wtxn, &BEI64::new(68), &())?;
+    db.put(&mut wtxn, &BEI64::new(35), &())?;
+    db.put(&mut wtxn, &BEI64::new(42), &())?;
 
     let rets: Result<Vec<(BEI64, _)>, _> = db.iter(&wtxn)?.collect();
 
@@ -153,10 +153,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<Str, Unit> = env.create_database(Some("str-range"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &"a", &())?;
-    let _ret = db.put(&mut wtxn, &"b", &())?;
-    let _ret = db.put(&mut wtxn, &"c", &())?;
-    let _ret = db.put(&mut wtxn, &"d", &())?;
+    db.put(&mut wtxn, &"a", &())?;
+    db.put(&mut wtxn, &"b", &())?;
+    db.put(&mut wtxn, &"c", &())?;
+    db.put(&mut wtxn, &"d", &())?;
 
     // or iterate over ranges too!!!
     let range = "a"..="f";
@@ -168,10 +168,10 @@ fn main() -> Result<(), Box<dyn Error>> {
     let db: Database<ByteSlice, Unit> = env.create_database(Some("bytes-range"))?;
 
     let mut wtxn = env.write_txn()?;
-    let _ret = db.put(&mut wtxn, &&[1][..], &())?;
-    let _ret = db.put(&mut wtxn, &&[2][..], &())?;
-    let _ret = db.put(&mut wtxn, &&[3][..], &())?;
-    let _ret = db.put(&mut wtxn, &&[4][..], &())?;
+    db.put(&mut wtxn, &&[1][..], &())?;
+    db.put(&mut wtxn, &&[2][..], &())?;
+    db.put(&mut wtxn, &&[3][..], &())?;
+    db.put(&mut wtxn, &&[4][..], &())?;
 
     // or iterate over ranges too!!!
     let range = &[1][..]..=&[10][..];
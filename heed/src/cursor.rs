@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::ops::{Deref, DerefMut};
 use std::{marker, mem, ptr};
 
@@ -7,6 +8,8 @@ use crate::mdb::ffi;
 
 pub struct RoCursor<'txn> {
     cursor: *mut ffi::MDB_cursor,
+    txn: *mut ffi::MDB_txn,
+    dbi: ffi::MDB_dbi,
     _marker: marker::PhantomData<&'txn ()>,
 }
 
@@ -18,10 +21,30 @@ impl<'txn> RoCursor<'txn> {
 
         Ok(RoCursor {
             cursor,
+            txn: txn.txn,
+            dbi,
             _marker: marker::PhantomData,
         })
     }
 
+    /// Compares two keys with the comparison function of the database,
+    /// either the default one or a custom [`Comparator`].
+    pub fn compare_keys(&self, a: &[u8], b: &[u8]) -> Ordering {
+        let a = unsafe { crate::into_val(a) };
+        let b = unsafe { crate::into_val(b) };
+        let result = unsafe { ffi::mdb_cmp(self.txn, self.dbi, &a, &b) };
+        result.cmp(&0)
+    }
+
+    /// Compares two duplicate data items with the comparison function of the database,
+    /// only meaningful for the databases created with the `MDB_DUPSORT` flag.
+    pub fn compare_data(&self, a: &[u8], b: &[u8]) -> Ordering {
+        let a = unsafe { crate::into_val(a) };
+        let b = unsafe { crate::into_val(b) };
+        let result = unsafe { ffi::mdb_dcmp(self.txn, self.dbi, &a, &b) };
+        result.cmp(&0)
+    }
+
     pub fn current(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
         let mut key_val = mem::MaybeUninit::uninit();
         let mut data_val = mem::MaybeUninit::uninit();
@@ -101,7 +124,7 @@ impl<'txn> RoCursor<'txn> {
         &mut self,
         key: &[u8],
     ) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
-        let mut key_val = unsafe { crate::into_val(&key) };
+        let mut key_val = unsafe { crate::into_val(key) };
         let mut data_val = mem::MaybeUninit::uninit();
 
         // Move the cursor to the specified key
@@ -174,6 +197,173 @@ impl<'txn> RoCursor<'txn> {
             Err(e) => Err(e.into()),
         }
     }
+
+    pub fn move_on_key(&mut self, key: &[u8]) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the specified key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                &mut key_val,
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_SET_KEY,
+            ))
+        };
+
+        match result {
+            Ok(()) => {
+                let key = unsafe { crate::from_val(key_val) };
+                let data = unsafe { crate::from_val(data_val.assume_init()) };
+                Ok(Some((key, data)))
+            }
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_first_dup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor on the first data item of the current key,
+        // this operation doesn't return the key so we read it back from the cursor.
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                ptr::null_mut(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_FIRST_DUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => self.current(),
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_last_dup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor on the last data item of the current key,
+        // this operation doesn't return the key so we read it back from the cursor.
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                ptr::null_mut(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_LAST_DUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => self.current(),
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_next_dup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = mem::MaybeUninit::uninit();
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the next data item of the current key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                key_val.as_mut_ptr(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_NEXT_DUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => {
+                let key = unsafe { crate::from_val(key_val.assume_init()) };
+                let data = unsafe { crate::from_val(data_val.assume_init()) };
+                Ok(Some((key, data)))
+            }
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_prev_dup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = mem::MaybeUninit::uninit();
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the previous data item of the current key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                key_val.as_mut_ptr(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_PREV_DUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => {
+                let key = unsafe { crate::from_val(key_val.assume_init()) };
+                let data = unsafe { crate::from_val(data_val.assume_init()) };
+                Ok(Some((key, data)))
+            }
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_next_nodup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = mem::MaybeUninit::uninit();
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the first data item of the next key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                key_val.as_mut_ptr(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_NEXT_NODUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => {
+                let key = unsafe { crate::from_val(key_val.assume_init()) };
+                let data = unsafe { crate::from_val(data_val.assume_init()) };
+                Ok(Some((key, data)))
+            }
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn move_on_prev_nodup(&mut self) -> Result<Option<(&'txn [u8], &'txn [u8])>> {
+        let mut key_val = mem::MaybeUninit::uninit();
+        let mut data_val = mem::MaybeUninit::uninit();
+
+        // Move the cursor to the last data item of the previous key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_get(
+                self.cursor,
+                key_val.as_mut_ptr(),
+                data_val.as_mut_ptr(),
+                ffi::cursor_op::MDB_PREV_NODUP,
+            ))
+        };
+
+        match result {
+            Ok(()) => {
+                let key = unsafe { crate::from_val(key_val.assume_init()) };
+                let data = unsafe { crate::from_val(data_val.assume_init()) };
+                Ok(Some((key, data)))
+            }
+            Err(e) if e.not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 impl Drop for RoCursor<'_> {
@@ -205,8 +395,8 @@ impl<'txn> RwCursor<'txn> {
     }
 
     pub fn put_current(&mut self, key: &[u8], data: &[u8]) -> Result<bool> {
-        let mut key_val = unsafe { crate::into_val(&key) };
-        let mut data_val = unsafe { crate::into_val(&data) };
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
 
         // Modify the pointed data
         let result = unsafe {
@@ -225,9 +415,50 @@ impl<'txn> RwCursor<'txn> {
         }
     }
 
+    pub fn put(&mut self, key: &[u8], data: &[u8]) -> Result<()> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
+
+        // Insert the entry and move the cursor on it
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_put(
+                self.cursor.cursor,
+                &mut key_val,
+                &mut data_val,
+                0,
+            ))
+        };
+
+        result.map_err(Into::into)
+    }
+
+    /// Inserts the data item of the key only if it isn't already stored (`MDB_NODUPDATA`),
+    /// returns `false` otherwise, only meaningful for the databases created with the
+    /// `MDB_DUPSORT` flag.
+    pub fn put_dup_if_absent(&mut self, key: &[u8], data: &[u8]) -> Result<bool> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
+
+        // Insert the entry and move the cursor on it
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_put(
+                self.cursor.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::MDB_NODUPDATA,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(MdbError::KeyExist) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn append(&mut self, key: &[u8], data: &[u8]) -> Result<()> {
-        let mut key_val = unsafe { crate::into_val(&key) };
-        let mut data_val = unsafe { crate::into_val(&data) };
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
 
         // Modify the pointed data
         let result = unsafe {
@@ -241,6 +472,23 @@ impl<'txn> RwCursor<'txn> {
 
         result.map_err(Into::into)
     }
+
+    pub fn append_dup(&mut self, key: &[u8], data: &[u8]) -> Result<()> {
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = unsafe { crate::into_val(data) };
+
+        // Append the data to the duplicates of the key
+        let result = unsafe {
+            mdb_result(ffi::mdb_cursor_put(
+                self.cursor.cursor,
+                &mut key_val,
+                &mut data_val,
+                ffi::MDB_APPENDDUP,
+            ))
+        };
+
+        result.map_err(Into::into)
+    }
 }
 
 impl<'txn> Deref for RwCursor<'txn> {
@@ -1,9 +1,11 @@
 use std::ops::Bound;
-use std::borrow::Cow;
-use std::{marker, mem, ptr};
+use std::cmp::Ordering;
+use std::borrow::{Borrow, Cow};
+use std::{io, marker, mem, ptr};
 use std::ops::RangeBounds;
 
 use crate::*;
+use crate::env::check_prefix_ordering;
 use crate::mdb::error::mdb_result;
 use crate::mdb::ffi;
 use crate::types::DecodeIgnore;
@@ -119,8 +121,8 @@ use crate::types::DecodeIgnore;
 /// # Ok(()) }
 /// ```
 pub struct Database<KC, DC> {
-    env_ident: usize,
-    dbi: ffi::MDB_dbi,
+    pub(crate) env_ident: usize,
+    pub(crate) dbi: ffi::MDB_dbi,
     marker: marker::PhantomData<(KC, DC)>,
 }
 
@@ -227,7 +229,7 @@ impl<KC, DC> Database<KC, DC> {
     {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
 
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut data_val = mem::MaybeUninit::uninit();
@@ -252,6 +254,126 @@ impl<KC, DC> Database<KC, DC> {
         }
     }
 
+    /// Returns an iterator over all the data items of the given key,
+    /// ordered from the lowest to the highest.
+    ///
+    /// If the key does not exist, then `None` is returned.
+    ///
+    /// This method is intended to be used on databases created with the `MDB_DUPSORT` flag
+    /// (see [`Env::create_dup_database`]), on a database without this flag the iterator
+    /// only returns the single data item of the key.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_dup_database(Some("dup-get-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-also-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    ///
+    /// let mut iter = db.get_duplicates(&wtxn, &BEI32::new(42))?.unwrap();
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-also-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    /// drop(iter);
+    ///
+    /// let ret = db.get_duplicates(&wtxn, &BEI32::new(13))?;
+    /// assert!(ret.is_none());
+    /// drop(ret);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn get_duplicates<'txn, T>(
+        &self,
+        txn: &'txn RoTxn<T>,
+        key: &KC::EItem,
+    ) -> Result<Option<RoDupIter<'txn, KC, DC>>>
+    where
+        KC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        match cursor.move_on_key(&key_bytes)? {
+            Some(_) => Ok(Some(RoDupIter::new(cursor))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a reversed iterator over all the data items of the given key,
+    /// ordered from the highest to the lowest.
+    ///
+    /// If the key does not exist, then `None` is returned.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_dup_database(Some("dup-rev-get-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-also-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    ///
+    /// let mut iter = db.rev_get_duplicates(&wtxn, &BEI32::new(42))?.unwrap();
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-also-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    /// drop(iter);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_get_duplicates<'txn, T>(
+        &self,
+        txn: &'txn RoTxn<T>,
+        key: &KC::EItem,
+    ) -> Result<Option<RoRevDupIter<'txn, KC, DC>>>
+    where
+        KC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+
+        let mut cursor = RoCursor::new(txn, self.dbi)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        match cursor.move_on_key(&key_bytes)? {
+            Some(_) => Ok(Some(RoRevDupIter::new(cursor))),
+            None => Ok(None),
+        }
+    }
+
     /// Retrieves the key/value pair lower than the given one in this database.
     ///
     /// If the database if empty or there is no key lower than the given one,
@@ -307,7 +429,7 @@ impl<KC, DC> Database<KC, DC> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
         cursor.move_on_key_greater_than_or_equal_to(&key_bytes)?;
 
         match cursor.move_on_prev() {
@@ -375,9 +497,11 @@ impl<KC, DC> Database<KC, DC> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
         let result = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
-            Ok(Some((key, data))) if key == &key_bytes[..] => Ok(Some((key, data))),
+            Ok(Some((key, data))) if cursor.compare_keys(key, &key_bytes) == Ordering::Equal => {
+                Ok(Some((key, data)))
+            }
             Ok(_) => cursor.move_on_prev(),
             Err(e) => Err(e),
         };
@@ -447,10 +571,12 @@ impl<KC, DC> Database<KC, DC> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
         let entry = match cursor.move_on_key_greater_than_or_equal_to(&key_bytes)? {
-            Some((key, data)) if key > &key_bytes[..] => Some((key, data)),
-            Some((_key, _data)) => cursor.move_on_next()?,
+            Some((key, data)) if cursor.compare_keys(key, &key_bytes) == Ordering::Greater => {
+                Some((key, data))
+            }
+            Some((_key, _data)) => cursor.move_on_next_nodup()?,
             None => None,
         };
 
@@ -518,7 +644,7 @@ impl<KC, DC> Database<KC, DC> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
         let mut cursor = RoCursor::new(txn, self.dbi)?;
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
         match cursor.move_on_key_greater_than_or_equal_to(&key_bytes) {
             Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
                 (Ok(key), Ok(data)) => Ok(Some((key, data))),
@@ -674,10 +800,49 @@ impl<KC, DC> Database<KC, DC> {
     ///
     /// # Ok(()) }
     /// ```
-    pub fn len<'txn, T>(&self, txn: &'txn RoTxn<T>) -> Result<u64> {
+    pub fn len<T>(&self, txn: &RoTxn<T>) -> Result<u64> {
+        self.stat(txn).map(|stat| stat.entries)
+    }
+
+    /// Returns the statistics of this database: the page size, the depth of the
+    /// B-tree, the number of branch, leaf and overflow pages and the number of entries.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_database(Some("stat-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    ///
+    /// let stat = db.stat(&wtxn)?;
+    /// assert_eq!(stat.entries, 2);
+    /// assert_eq!(stat.depth, 1);
+    /// assert_eq!(stat.leaf_pages, 1);
+    /// assert_eq!(stat.overflow_pages, 0);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn stat<T>(&self, txn: &RoTxn<T>) -> Result<DatabaseStat> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
 
-        let mut db_stat = mem::MaybeUninit::uninit();
+        let mut db_stat = mem::MaybeUninit::<ffi::MDB_Stat>::uninit();
 
         #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
         let result = unsafe {
@@ -702,7 +867,7 @@ impl<KC, DC> Database<KC, DC> {
         match result {
             Ok(()) => {
                 let stats = unsafe { db_stat.assume_init() };
-                Ok(stats.ms_entries as u64)
+                Ok(DatabaseStat::from_raw(stats))
             }
             Err(e) => Err(e.into()),
         }
@@ -746,7 +911,7 @@ impl<KC, DC> Database<KC, DC> {
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn is_empty<'txn, T>(&self, txn: &'txn RoTxn<T>) -> Result<bool> {
+    pub fn is_empty<T>(&self, txn: &RoTxn<T>) -> Result<bool> {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
         let mut cursor = RoCursor::new(txn, self.dbi)?;
         match cursor.move_on_first()? {
@@ -946,6 +1111,91 @@ impl<KC, DC> Database<KC, DC> {
         RwCursor::new(txn, self.dbi).map(|cursor| RwRevIter::new(cursor))
     }
 
+    /// Return a lexicographically ordered iterator over the distinct keys of this database,
+    /// every key is returned with its lowest data item.
+    ///
+    /// This method is intended to be used on databases created with the `MDB_DUPSORT` flag
+    /// (see [`Env::create_dup_database`]), it is equivalent to [`Database::iter`] otherwise.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_dup_database(Some("dup-iter-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-also-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    ///
+    /// let mut iter = db.iter_nodup(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(27), "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-also-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn iter_nodup<'txn, T>(&self, txn: &'txn RoTxn<T>) -> Result<RoNoDupIter<'txn, KC, DC>> {
+        assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+        RoCursor::new(txn, self.dbi).map(RoNoDupIter::new)
+    }
+
+    /// Return a reversed lexicographically ordered iterator over the distinct keys of this
+    /// database, every key is returned with its highest data item.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_dup_database(Some("dup-rev-iter-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-also-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    ///
+    /// let mut iter = db.rev_iter_nodup(&wtxn)?;
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(42), "i-am-forty-two")));
+    /// assert_eq!(iter.next().transpose()?, Some((BEI32::new(27), "i-am-twenty-seven")));
+    /// assert_eq!(iter.next().transpose()?, None);
+    ///
+    /// drop(iter);
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn rev_iter_nodup<'txn, T>(&self, txn: &'txn RoTxn<T>) -> Result<RoRevNoDupIter<'txn, KC, DC>> {
+        assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+        RoCursor::new(txn, self.dbi).map(RoRevNoDupIter::new)
+    }
+
     /// Return a lexicographically ordered iterator of a range of key-value pairs in this database.
     ///
     /// Comparisons are made by using the bytes representation of the key.
@@ -1333,6 +1583,7 @@ impl<KC, DC> Database<KC, DC> {
         KC: BytesEncode,
     {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+        check_prefix_ordering(txn, self.dbi)?;
         let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
         let prefix_bytes = prefix_bytes.into_owned();
         RoCursor::new(txn, self.dbi).map(|cursor| RoPrefix::new(cursor, prefix_bytes))
@@ -1401,6 +1652,7 @@ impl<KC, DC> Database<KC, DC> {
         KC: BytesEncode,
     {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+        check_prefix_ordering(txn, self.dbi)?;
         let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
         let prefix_bytes = prefix_bytes.into_owned();
         RwCursor::new(txn, self.dbi).map(|cursor| RwPrefix::new(cursor, prefix_bytes))
@@ -1456,6 +1708,7 @@ impl<KC, DC> Database<KC, DC> {
         KC: BytesEncode,
     {
         assert_eq!(self.env_ident, txn.env.env_mut_ptr() as usize);
+        check_prefix_ordering(txn, self.dbi)?;
         let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
         let prefix_bytes = prefix_bytes.into_owned();
         RoCursor::new(txn, self.dbi).map(|cursor| RoRevPrefix::new(cursor, prefix_bytes))
@@ -1524,6 +1777,7 @@ impl<KC, DC> Database<KC, DC> {
         KC: BytesEncode,
     {
         assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+        check_prefix_ordering(txn, self.dbi)?;
         let prefix_bytes = KC::bytes_encode(prefix).map_err(Error::Encoding)?;
         let prefix_bytes = prefix_bytes.into_owned();
         RwCursor::new(txn, self.dbi).map(|cursor| RwRevPrefix::new(cursor, prefix_bytes))
@@ -1569,8 +1823,8 @@ impl<KC, DC> Database<KC, DC> {
     {
         assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
 
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
         let mut data_val = unsafe { crate::into_val(&data_bytes) };
@@ -1589,18 +1843,22 @@ impl<KC, DC> Database<KC, DC> {
         Ok(())
     }
 
-    /// Append the given key/data pair to the end of the database.
+    /// Inserts a key with a value of `len` bytes that `write` fills directly in the memory map
+    /// (`MDB_RESERVE`), without encoding the value in an intermediate buffer first.
     ///
-    /// This option allows fast bulk loading when keys are already known to be in the correct order.
-    /// Loading unsorted keys will cause a MDB_KEYEXIST error.
+    /// The buffer given to `write` is zeroed, the bytes it doesn't write are stored as zeroes.
+    /// The value is already replaced when `write` is called, if it fails its error is returned,
+    /// the key keeps the partially written value and the write transaction must be aborted.
+    /// The databases created with the `MDB_DUPSORT` flag don't support reserved writes, an
+    /// [`Error::InvalidDatabaseFlags`] is returned for them.
     ///
     /// ```
     /// # use std::fs;
     /// # use std::path::Path;
     /// # use heed::EnvOpenOptions;
+    /// use std::io::Write;
     /// use heed::Database;
     /// use heed::types::*;
-    /// use heed::byteorder::BigEndian;
     ///
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
@@ -1608,36 +1866,104 @@ impl<KC, DC> Database<KC, DC> {
     /// #     .map_size(10 * 1024 * 1024) // 10MB
     /// #     .max_dbs(3000)
     /// #     .open(Path::new("target").join("database.mdb"))?;
-    /// type BEI32 = I32<BigEndian>;
+    /// let db: Database<Str, ByteSlice> = env.create_database(Some("put-reserved"))?;
     ///
-    /// let db: Database<OwnedType<BEI32>, Str> = env.create_database(Some("iter-i32"))?;
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put_reserved(&mut wtxn, &"zeroes", 4096, |mut buffer| {
+    ///     buffer.write_all(&[0; 4096])
+    /// })?;
+    ///
+    /// assert_eq!(db.get(&wtxn, &"zeroes")?, Some(&[0; 4096][..]));
+    /// wtxn.commit()?;
+    ///
+    /// // A failed write leaves a partially written value, the transaction must be aborted.
+    /// let mut wtxn = env.write_txn()?;
+    /// let ret = db.put_reserved(&mut wtxn, &"zeroes", 10, |mut buffer| {
+    ///     buffer.write_all(&[1; 20])
+    /// });
+    /// assert!(ret.is_err());
+    /// wtxn.abort()?;
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// assert_eq!(db.get(&rtxn, &"zeroes")?, Some(&[0; 4096][..]));
+    /// # Ok(()) }
+    /// ```
+    pub fn put_reserved<T, F>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, len: usize, write: F) -> Result<()>
+    where
+        KC: BytesEncode,
+        F: FnOnce(&mut [u8]) -> io::Result<()>,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        self.raw_put_reserved(txn, &key_bytes, len, |buffer| write(buffer).map_err(Error::Io))
+    }
+
+    /// Insert a key-value pair in this database, encoding the value directly in the memory map
+    /// when the data codec knows its size with [`BytesEncode::encoded_size`].
+    ///
+    /// The value is encoded with [`BytesEncode::bytes_encode_into`] once its space is reserved,
+    /// if it fails the key keeps a partially encoded value and the write transaction must be
+    /// aborted. The codecs that don't know the size of the encoded value fall back to
+    /// [`Database::put`]. The databases created with the `MDB_DUPSORT` flag
+    /// don't support it, an [`Error::InvalidDatabaseFlags`] is returned for them.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, SerdeBincode<(u32, String)>> = env.create_database(Some("in-place"))?;
     ///
     /// let mut wtxn = env.write_txn()?;
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &BEI32::new(13), &"i-am-thirteen")?;
-    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
-    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
-    /// db.put(&mut wtxn, &BEI32::new(521), &"i-am-five-hundred-and-twenty-one")?;
+    /// db.put_encoded_in_place(&mut wtxn, &"forty-two", &(42, String::from("i-am-forty-two")))?;
     ///
-    /// let ret = db.get(&mut wtxn, &BEI32::new(27))?;
-    /// assert_eq!(ret, Some("i-am-twenty-seven"));
+    /// let ret = db.get(&wtxn, &"forty-two")?;
+    /// assert_eq!(ret, Some((42, String::from("i-am-forty-two"))));
     ///
     /// wtxn.commit()?;
     /// # Ok(()) }
     /// ```
-    pub fn append<T>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, data: &DC::EItem) -> Result<()>
+    pub fn put_encoded_in_place<T>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, data: &DC::EItem) -> Result<()>
     where
         KC: BytesEncode,
         DC: BytesEncode,
     {
         assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let len = match DC::encoded_size(data) {
+            Some(len) => len,
+            None => return self.put(txn, key, data),
+        };
 
-        let mut key_val = unsafe { crate::into_val(&key_bytes) };
-        let mut data_val = unsafe { crate::into_val(&data_bytes) };
-        let flags = ffi::MDB_APPEND;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        self.raw_put_reserved(txn, &key_bytes, len, |buffer| {
+            DC::bytes_encode_into(data, buffer).map_err(Error::Encoding)
+        })
+    }
+
+    fn raw_put_reserved<T, F>(&self, txn: &mut RwTxn<T>, key: &[u8], len: usize, write: F) -> Result<()>
+    where
+        F: FnOnce(&mut [u8]) -> Result<()>,
+    {
+        // The reserved space of a duplicate data item can't be written afterwards.
+        if env::dbi_flags(txn, self.dbi)? & ffi::MDB_DUPSORT != 0 {
+            return Err(Error::InvalidDatabaseFlags);
+        }
+
+        let mut key_val = unsafe { crate::into_val(key) };
+        let mut data_val = ffi::reserve_val(len);
+        let flags = ffi::MDB_RESERVE;
 
         unsafe {
             mdb_result(ffi::mdb_put(
@@ -1649,12 +1975,16 @@ impl<KC, DC> Database<KC, DC> {
             ))?
         }
 
-        Ok(())
+        // The reserved space is uninitialized, it is zeroed before being given to `write`.
+        let buffer = unsafe { ffi::from_val_mut(data_val) };
+        buffer.fill(0);
+        write(buffer)
     }
 
-    /// Deletes a key-value pairs in this database.
+    /// Append the given key/data pair to the end of the database.
     ///
-    /// If the key does not exist, then `false` is returned.
+    /// This option allows fast bulk loading when keys are already known to be in the correct order.
+    /// Loading unsorted keys will cause a MDB_KEYEXIST error.
     ///
     /// ```
     /// # use std::fs;
@@ -1676,13 +2006,589 @@ impl<KC, DC> Database<KC, DC> {
     ///
     /// let mut wtxn = env.write_txn()?;
     /// # db.clear(&mut wtxn)?;
-    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
-    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
     /// db.put(&mut wtxn, &BEI32::new(13), &"i-am-thirteen")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
     /// db.put(&mut wtxn, &BEI32::new(521), &"i-am-five-hundred-and-twenty-one")?;
     ///
-    /// let ret = db.delete(&mut wtxn, &BEI32::new(27))?;
-    /// assert_eq!(ret, true);
+    /// let ret = db.get(&mut wtxn, &BEI32::new(27))?;
+    /// assert_eq!(ret, Some("i-am-twenty-seven"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn append<T>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, data: &DC::EItem) -> Result<()>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut data_val = unsafe { crate::into_val(&data_bytes) };
+        let flags = ffi::MDB_APPEND;
+
+        unsafe {
+            mdb_result(ffi::mdb_put(
+                txn.txn.txn,
+                self.dbi,
+                &mut key_val,
+                &mut data_val,
+                flags,
+            ))?
+        }
+
+        Ok(())
+    }
+
+    /// Inserts many key-value pairs in this database and returns the number of entries written.
+    ///
+    /// The entries are sorted by their encoded keys, with the comparison function of the
+    /// database, and only the last value of a key is kept, like with successive calls to
+    /// [`Database::put`]. If the database has been created with the `MDB_DUPSORT` flag,
+    /// all the distinct data items of a key are kept and the ones that were already
+    /// stored are not counted as written.
+    ///
+    /// The entries whose keys are greater than the last key of the database are appended
+    /// (`MDB_APPEND`), which is much faster, the other ones are inserted with regular puts.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(Some("put-many"))?;
+    /// let dups: Database<Str, Str> = env.create_dup_database(Some("put-many-dups"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// # dups.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &"c", &"old")?;
+    ///
+    /// let entries = vec![("d", "four"), ("a", "one"), ("c", "three"), ("b", "two"), ("a", "uno")];
+    /// let written = db.put_many(&mut wtxn, entries.iter().map(|(k, v)| (k, v)))?;
+    /// assert_eq!(written, 4);
+    ///
+    /// let rets: Vec<_> = db.iter(&wtxn)?.collect::<Result<_, _>>()?;
+    /// assert_eq!(rets, vec![("a", "uno"), ("b", "two"), ("c", "three"), ("d", "four")]);
+    ///
+    /// // The distinct data items of the keys are kept in the duplicate databases.
+    /// dups.put(&mut wtxn, &"b", &"blue")?;
+    ///
+    /// let entries = vec![("c", "cyan"), ("b", "black"), ("c", "coral"), ("b", "blue"), ("c", "cyan")];
+    /// let written = dups.put_many(&mut wtxn, entries.iter().map(|(k, v)| (k, v)))?;
+    /// // The ("b", "blue") pair was already stored.
+    /// assert_eq!(written, 3);
+    ///
+    /// let rets: Vec<_> = dups.iter(&wtxn)?.collect::<Result<_, _>>()?;
+    /// assert_eq!(rets, vec![("b", "black"), ("b", "blue"), ("c", "coral"), ("c", "cyan")]);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_many<'a, T, I>(&self, txn: &mut RwTxn<T>, items: I) -> Result<usize>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode,
+        KC::EItem: 'a,
+        DC::EItem: 'a,
+        I: IntoIterator<Item = (&'a KC::EItem, &'a DC::EItem)>,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let mut entries = Vec::new();
+        for (key, data) in items {
+            let key_bytes = KC::bytes_encode(key).map_err(Error::Encoding)?.into_owned();
+            let data_bytes = DC::bytes_encode(data).map_err(Error::Encoding)?.into_owned();
+            entries.push((key_bytes, data_bytes));
+        }
+
+        let dup_sort = env::dbi_flags(txn, self.dbi)? & ffi::MDB_DUPSORT != 0;
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+
+        let compare = |(a_key, a_data): &(Vec<u8>, Vec<u8>), (b_key, b_data): &(Vec<u8>, Vec<u8>)| {
+            match cursor.compare_keys(a_key, b_key) {
+                Ordering::Equal if dup_sort => cursor.compare_data(a_data, b_data),
+                ordering => ordering,
+            }
+        };
+
+        if entries.windows(2).any(|pair| compare(&pair[0], &pair[1]) == Ordering::Greater) {
+            entries.sort_by(compare);
+        }
+
+        // The sort is stable, we keep the last value of the equal entries.
+        entries.dedup_by(|next, previous| {
+            let equal = compare(previous, next) == Ordering::Equal;
+            if equal {
+                mem::swap(next, previous);
+            }
+            equal
+        });
+
+        // The entries greater than the last key of the database can be appended.
+        let first_appendable = match cursor.move_on_last()? {
+            Some((last, _)) => entries.partition_point(|(key, _)| cursor.compare_keys(key, last) != Ordering::Greater),
+            None => 0,
+        };
+
+        let mut written = entries.len() - first_appendable;
+        for (key, data) in &entries[..first_appendable] {
+            if !dup_sort {
+                cursor.put(key, data)?;
+                written += 1;
+            } else if cursor.put_dup_if_absent(key, data)? {
+                written += 1;
+            }
+        }
+
+        let mut previous_key: Option<&[u8]> = None;
+        for (key, data) in &entries[first_appendable..] {
+            match previous_key {
+                Some(previous) if dup_sort && cursor.compare_keys(previous, key) == Ordering::Equal => {
+                    cursor.append_dup(key, data)?
+                }
+                _ => cursor.append(key, data)?,
+            }
+            previous_key = Some(key);
+        }
+
+        Ok(written)
+    }
+
+    /// Inserts a key-value pair in this database only if the key doesn't already exist
+    /// (`MDB_NOOVERWRITE`), otherwise returns the existing value and leaves it untouched.
+    ///
+    /// If the database has been created with the `MDB_DUPSORT` flag, the first data item
+    /// of an existing key is returned, see [`Database::put_dup_if_absent`].
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(Some("put-if-absent"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// assert_eq!(db.put_if_absent(&mut wtxn, &"paris", &"france")?, None);
+    /// assert_eq!(db.put_if_absent(&mut wtxn, &"paris", &"texas")?, Some("france"));
+    ///
+    /// assert_eq!(db.get(&wtxn, &"paris")?, Some("france"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_if_absent<'txn, T>(
+        &self,
+        txn: &'txn mut RwTxn<T>,
+        key: &KC::EItem,
+        data: &DC::EItem,
+    ) -> Result<Option<DC::DItem>>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode + BytesDecode<'txn>,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut data_val = unsafe { crate::into_val(&data_bytes) };
+        let flags = ffi::MDB_NOOVERWRITE;
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_put(
+                txn.txn.txn,
+                self.dbi,
+                &mut key_val,
+                &mut data_val,
+                flags,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(None),
+            // LMDB points the data to the existing value.
+            Err(MdbError::KeyExist) => {
+                let data = unsafe { crate::from_val(data_val) };
+                let data = DC::bytes_decode(data).map_err(Error::Decoding)?;
+                Ok(Some(data))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Inserts a data item for the given key only if this key doesn't already have it
+    /// (`MDB_NODUPDATA`), returns `false` if the key already has this data item.
+    ///
+    /// It can only be used with databases created with the `MDB_DUPSORT` flag.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_dup_database(Some("put-dup-if-absent"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// assert!(db.put_dup_if_absent(&mut wtxn, &"colors", &"red")?);
+    /// assert!(db.put_dup_if_absent(&mut wtxn, &"colors", &"blue")?);
+    /// assert!(!db.put_dup_if_absent(&mut wtxn, &"colors", &"red")?);
+    ///
+    /// let colors: Vec<_> = db.get_duplicates(&wtxn, &"colors")?.unwrap().collect::<Result<_, _>>()?;
+    /// assert_eq!(colors, vec![("colors", "blue"), ("colors", "red")]);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn put_dup_if_absent<T>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, data: &DC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut data_val = unsafe { crate::into_val(&data_bytes) };
+        let flags = ffi::MDB_NODUPDATA;
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_put(
+                txn.txn.txn,
+                self.dbi,
+                &mut key_val,
+                &mut data_val,
+                flags,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(MdbError::KeyExist) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the value of the given key, or inserts and returns the value
+    /// computed by `f` if the key doesn't exist, `f` is only called in this case.
+    ///
+    /// The key is only looked up once, the cursor positioned on it is used to insert
+    /// the value. Nothing is inserted if `f` returns an error, which is returned.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(Some("get-or-insert-with"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// let ret = db.get_or_insert_with(&mut wtxn, &"greeting", || Ok("hello"))?;
+    /// assert_eq!(ret, "hello");
+    ///
+    /// let ret = db.get_or_insert_with(&mut wtxn, &"greeting", || unreachable!())?;
+    /// assert_eq!(ret, "hello");
+    ///
+    /// // A failure of `f` aborts the insert.
+    /// let ret = db.get_or_insert_with(&mut wtxn, &"farewell", || Err(heed::Error::DatabaseClosing));
+    /// assert!(ret.is_err());
+    /// assert_eq!(db.get(&wtxn, &"farewell")?, None);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn get_or_insert_with<'txn, T, F>(
+        &self,
+        txn: &'txn mut RwTxn<T>,
+        key: &KC::EItem,
+        f: F,
+    ) -> Result<DC::DItem>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode + BytesDecode<'txn>,
+        DC::EItem: Sized,
+        F: FnOnce() -> Result<DC::EItem>,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+
+        let data = match cursor.move_on_key(&key_bytes)? {
+            Some((_, data)) => data,
+            None => {
+                let data = f()?;
+                let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+                cursor.put(&key_bytes, &data_bytes)?;
+
+                // The returned value must borrow from the transaction, not from the encoded bytes,
+                // the cursor is positioned on the inserted entry.
+                match cursor.current()? {
+                    Some((_, data)) => data,
+                    None => return Err(Error::Mdb(MdbError::NotFound)),
+                }
+            }
+        };
+
+        DC::bytes_decode(data).map_err(Error::Decoding)
+    }
+
+    /// Updates the value of the given key with the one returned by `f`, which is given
+    /// the current value, if any. Returning `None` deletes the key.
+    ///
+    /// The new value can be anything that borrows as the encoded item of the data codec,
+    /// like the item itself or a reference to it.
+    ///
+    /// The key is only looked up once, the cursor positioned on it is used to replace
+    /// or delete the value. The databases created with the `MDB_DUPSORT` flag are not
+    /// supported, an [`Error::InvalidDatabaseFlags`] is returned for them.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEU64 = U64<BigEndian>;
+    ///
+    /// let db: Database<Str, OwnedType<BEU64>> = env.create_database(Some("update"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// for _ in 0..3 {
+    ///     db.update(&mut wtxn, &"visits", |count| {
+    ///         let count = count.map_or(0, |c| c.get());
+    ///         Some(BEU64::new(count + 1))
+    ///     })?;
+    /// }
+    /// assert_eq!(db.get(&wtxn, &"visits")?, Some(BEU64::new(3)));
+    ///
+    /// db.update(&mut wtxn, &"visits", |_| None::<BEU64>)?;
+    /// assert_eq!(db.get(&wtxn, &"visits")?, None);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn update<'txn, T, B, F>(&self, txn: &'txn mut RwTxn<T>, key: &KC::EItem, f: F) -> Result<()>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode + BytesDecode<'txn>,
+        B: Borrow<DC::EItem>,
+        F: FnOnce(Option<DC::DItem>) -> Option<B>,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        if env::dbi_flags(txn, self.dbi)? & ffi::MDB_DUPSORT != 0 {
+            return Err(Error::InvalidDatabaseFlags);
+        }
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+
+        let old = match cursor.move_on_key(&key_bytes)? {
+            Some((_, data)) => Some(DC::bytes_decode(data).map_err(Error::Decoding)?),
+            None => None,
+        };
+        let exists = old.is_some();
+
+        match f(old) {
+            Some(new) => {
+                // The new value may borrow from the current one, in the memory map, which
+                // is moved while it is replaced, it is copied before being written.
+                let data_bytes = DC::bytes_encode(new.borrow()).map_err(Error::Encoding)?.into_owned();
+                if exists {
+                    cursor.put_current(&key_bytes, &data_bytes)?;
+                } else {
+                    cursor.put(&key_bytes, &data_bytes)?;
+                }
+            }
+            None if exists => {
+                cursor.del_current()?;
+            }
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the value of the given key with `new` only if its current value is `expected`,
+    /// returns `false` and leaves the key untouched otherwise.
+    ///
+    /// An `expected` value of `None` means that the key must not exist and a `new` value
+    /// of `None` deletes the key. The values are compared by their encoded bytes.
+    ///
+    /// The key is only looked up once, the cursor positioned on it is used to replace
+    /// or delete the value. The databases created with the `MDB_DUPSORT` flag are not
+    /// supported, an [`Error::InvalidDatabaseFlags`] is returned for them.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(Some("compare-and-swap"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// assert!(db.compare_and_swap(&mut wtxn, &"owner", None, Some(&"alice"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, &"owner", None, Some(&"bob"))?);
+    /// assert!(!db.compare_and_swap(&mut wtxn, &"owner", Some(&"bob"), None)?);
+    /// assert!(db.compare_and_swap(&mut wtxn, &"owner", Some(&"alice"), Some(&"bob"))?);
+    /// assert_eq!(db.get(&wtxn, &"owner")?, Some("bob"));
+    ///
+    /// assert!(db.compare_and_swap(&mut wtxn, &"owner", Some(&"bob"), None)?);
+    /// assert_eq!(db.get(&wtxn, &"owner")?, None);
+    /// wtxn.commit()?;
+    ///
+    /// // The databases with duplicate data items are rejected.
+    /// let dups = env.database_options().types::<Str, Str>().name("cas-dups").dup_sort(true).create()?;
+    /// let mut wtxn = env.write_txn()?;
+    /// let ret = dups.compare_and_swap(&mut wtxn, &"owner", None, Some(&"alice"));
+    /// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseFlags)));
+    /// let ret = dups.update(&mut wtxn, &"owner", |_| Some("alice"));
+    /// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseFlags)));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn compare_and_swap<T>(
+        &self,
+        txn: &mut RwTxn<T>,
+        key: &KC::EItem,
+        expected: Option<&DC::EItem>,
+        new: Option<&DC::EItem>,
+    ) -> Result<bool>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        if env::dbi_flags(txn, self.dbi)? & ffi::MDB_DUPSORT != 0 {
+            return Err(Error::InvalidDatabaseFlags);
+        }
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let expected_bytes = match expected {
+            Some(expected) => Some(DC::bytes_encode(expected).map_err(Error::Encoding)?),
+            None => None,
+        };
+        let new_bytes = match new {
+            Some(new) => Some(DC::bytes_encode(new).map_err(Error::Encoding)?),
+            None => None,
+        };
+
+        let mut cursor = RwCursor::new(txn, self.dbi)?;
+        let current = cursor.move_on_key(&key_bytes)?.map(|(_, data)| data);
+        if current != expected_bytes.as_deref() {
+            return Ok(false);
+        }
+
+        match (current, new_bytes) {
+            (Some(_), Some(new)) => {
+                cursor.put_current(&key_bytes, &new)?;
+            }
+            (None, Some(new)) => cursor.put(&key_bytes, &new)?,
+            (Some(_), None) => {
+                cursor.del_current()?;
+            }
+            (None, None) => (),
+        }
+
+        Ok(true)
+    }
+
+    /// Deletes a key-value pairs in this database.
+    ///
+    /// If the key does not exist, then `false` is returned.
+    ///
+    /// If the database has been created with the `MDB_DUPSORT` flag, all the data items
+    /// of the key are deleted, use [`Database::delete_one`] to delete a single one.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_database(Some("iter-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(27), &"i-am-twenty-seven")?;
+    /// db.put(&mut wtxn, &BEI32::new(13), &"i-am-thirteen")?;
+    /// db.put(&mut wtxn, &BEI32::new(521), &"i-am-five-hundred-and-twenty-one")?;
+    ///
+    /// let ret = db.delete(&mut wtxn, &BEI32::new(27))?;
+    /// assert_eq!(ret, true);
     ///
     /// let ret = db.get(&mut wtxn, &BEI32::new(27))?;
     /// assert_eq!(ret, None);
@@ -1699,7 +2605,7 @@ impl<KC, DC> Database<KC, DC> {
     {
         assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
 
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
         let mut key_val = unsafe { crate::into_val(&key_bytes) };
 
         let result = unsafe {
@@ -1718,6 +2624,78 @@ impl<KC, DC> Database<KC, DC> {
         }
     }
 
+    /// Deletes a single data item of a key in this database.
+    ///
+    /// This method is intended to be used on databases created with the `MDB_DUPSORT` flag
+    /// (see [`Env::create_dup_database`]), where [`Database::delete`] removes all the data
+    /// items of the key at once.
+    ///
+    /// If the key/data pair does not exist, then `false` is returned.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("database.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("database.mdb"))?;
+    /// type BEI32 = I32<BigEndian>;
+    ///
+    /// let db: Database<OwnedType<BEI32>, Str> = env.create_dup_database(Some("dup-delete-i32"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// db.put(&mut wtxn, &BEI32::new(42), &"i-am-also-forty-two")?;
+    ///
+    /// let ret = db.delete_one(&mut wtxn, &BEI32::new(42), &"i-am-forty-two")?;
+    /// assert_eq!(ret, true);
+    ///
+    /// let ret = db.delete_one(&mut wtxn, &BEI32::new(42), &"i-am-not-here")?;
+    /// assert_eq!(ret, false);
+    ///
+    /// let ret = db.get(&wtxn, &BEI32::new(42))?;
+    /// assert_eq!(ret, Some("i-am-also-forty-two"));
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn delete_one<T>(&self, txn: &mut RwTxn<T>, key: &KC::EItem, data: &DC::EItem) -> Result<bool>
+    where
+        KC: BytesEncode,
+        DC: BytesEncode,
+    {
+        assert_eq!(self.env_ident, txn.txn.env.env_mut_ptr() as usize);
+
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
+
+        let mut key_val = unsafe { crate::into_val(&key_bytes) };
+        let mut data_val = unsafe { crate::into_val(&data_bytes) };
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_del(
+                txn.txn.txn,
+                self.dbi,
+                &mut key_val,
+                &mut data_val,
+            ))
+        };
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(e) if e.not_found() => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Deletes a range of key-value pairs in this database.
     ///
     /// Perfer using [`clear`] instead of a call to this method with a full range ([`..`]).
@@ -1891,12 +2869,41 @@ impl<KC, DC> Database<KC, DC> {
 
 impl<KC, DC> Clone for Database<KC, DC> {
     fn clone(&self) -> Database<KC, DC> {
-        Database {
-            env_ident: self.env_ident,
-            dbi: self.dbi,
-            marker: marker::PhantomData,
-        }
+        *self
     }
 }
 
 impl<KC, DC> Copy for Database<KC, DC> {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn update_with_borrowed_value() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("update-with-borrowed-value.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .open(Path::new("target").join("update-with-borrowed-value.mdb")).unwrap();
+        let db = env.create_database::<Str, Str>(None).unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        db.clear(&mut wtxn).unwrap();
+        let value = "abcdefghijklmnopqrstuvwxyz".repeat(4);
+        db.put(&mut wtxn, &"a", &value.as_str()).unwrap();
+        let other = "0123456789".repeat(10);
+        for key in &["b", "c", "d", "e"] {
+            db.put(&mut wtxn, key, &other.as_str()).unwrap();
+        }
+
+        // The new value borrows from the old one, stored in a dirty page.
+        db.update(&mut wtxn, &"a", |old| old.map(|s| &s[10..])).unwrap();
+        assert_eq!(db.get(&wtxn, &"a").unwrap(), Some(&value[10..]));
+        assert_eq!(db.get(&wtxn, &"e").unwrap(), Some(other.as_str()));
+
+        wtxn.commit().unwrap();
+    }
+}
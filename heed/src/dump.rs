@@ -0,0 +1,262 @@
+use std::io::{self, BufRead, Write};
+
+use crate::env::{dbi_flags, open_dbi};
+use crate::mdb::error::mdb_result;
+use crate::mdb::ffi;
+use crate::*;
+
+/// The version of the `mdb_dump` format that is written.
+const DUMP_VERSION: u32 = 3;
+
+/// The database flags written in the header of a dump, with their names.
+const DB_FLAGS: [(u32, &str); 6] = [
+    (ffi::MDB_REVERSEKEY, "reversekey"),
+    (ffi::MDB_DUPSORT, "dupsort"),
+    (ffi::MDB_INTEGERKEY, "integerkey"),
+    (ffi::MDB_DUPFIXED, "dupfixed"),
+    (ffi::MDB_INTEGERDUP, "integerdup"),
+    (ffi::MDB_REVERSEDUP, "reversedup"),
+];
+
+/// How the keys and values are written by [`Env::dump`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// Every byte is written as two hexadecimal digits (`format=bytevalue`).
+    ByteValue,
+    /// The printable characters are written as is, the other bytes
+    /// are written as a backslash and two hexadecimal digits (`format=print`).
+    Print,
+}
+
+fn invalid_dump(line: usize, msg: &str) -> Error {
+    let msg = format!("invalid dump at line {}: {}", line, msg);
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8], format: DumpFormat) -> io::Result<()> {
+    writer.write_all(b" ")?;
+    for &byte in bytes {
+        match format {
+            DumpFormat::ByteValue => write!(writer, "{:02x}", byte)?,
+            DumpFormat::Print if byte == b'\\' => writer.write_all(b"\\\\")?,
+            DumpFormat::Print if byte.is_ascii_graphic() || byte == b' ' => writer.write_all(&[byte])?,
+            DumpFormat::Print => write!(writer, "\\{:02x}", byte)?,
+        }
+    }
+    writer.write_all(b"\n")
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|d| d as u8)
+}
+
+fn read_bytes(line: &str, format: DumpFormat) -> Option<Vec<u8>> {
+    let line = line.strip_prefix(' ')?.as_bytes();
+    let mut bytes = Vec::with_capacity(line.len() / 2);
+
+    match format {
+        DumpFormat::ByteValue => {
+            if line.len() % 2 != 0 {
+                return None;
+            }
+            for pair in line.chunks(2) {
+                bytes.push(hex_value(pair[0])? << 4 | hex_value(pair[1])?);
+            }
+        }
+        DumpFormat::Print => {
+            let mut iter = line.iter();
+            while let Some(&byte) = iter.next() {
+                if byte != b'\\' {
+                    bytes.push(byte);
+                } else {
+                    match iter.next()? {
+                        b'\\' => bytes.push(b'\\'),
+                        &high => bytes.push(hex_value(high)? << 4 | hex_value(*iter.next()?)?),
+                    }
+                }
+            }
+        }
+    }
+
+    Some(bytes)
+}
+
+/// Writes the given databases in the `mdb_dump` format.
+pub(crate) fn dump<W: Write>(
+    env: &Env,
+    mut writer: W,
+    databases: &[Option<&str>],
+    format: DumpFormat,
+) -> Result<()> {
+    let info = env.info()?;
+    let page_size = env.stat()?.page_size;
+    let rtxn = env.read_txn()?;
+
+    for &name in databases {
+        let dbi = open_dbi(&rtxn, name, 0)?.ok_or(Error::Mdb(MdbError::NotFound))?;
+        let flags = dbi_flags(&rtxn, dbi)?;
+
+        writeln!(writer, "VERSION={}", DUMP_VERSION)?;
+        match format {
+            DumpFormat::ByteValue => writeln!(writer, "format=bytevalue")?,
+            DumpFormat::Print => writeln!(writer, "format=print")?,
+        }
+        if let Some(name) = name {
+            writeln!(writer, "database={}", name)?;
+        }
+        writeln!(writer, "type=btree")?;
+        writeln!(writer, "mapsize={}", info.map_size)?;
+        writeln!(writer, "maxreaders={}", info.max_readers)?;
+        if flags & ffi::MDB_DUPSORT != 0 {
+            writeln!(writer, "duplicates=1")?;
+        }
+        for (flag, flag_name) in DB_FLAGS.iter() {
+            if flags & flag != 0 {
+                writeln!(writer, "{}=1", flag_name)?;
+            }
+        }
+        writeln!(writer, "db_pagesize={}", page_size)?;
+        writeln!(writer, "HEADER=END")?;
+
+        let mut cursor = RoCursor::new(&rtxn, dbi)?;
+        let mut entry = cursor.move_on_first()?;
+        while let Some((key, data)) = entry {
+            // The unnamed database also stores the named ones, they are dumped separately.
+            let is_database = name.is_none() && unsafe { ffi::is_database_key(key) };
+
+            if !is_database {
+                write_bytes(&mut writer, key, format)?;
+                write_bytes(&mut writer, data, format)?;
+            }
+            entry = cursor.move_on_next()?;
+        }
+
+        writeln!(writer, "DATA=END")?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// The header of a database in a dump.
+struct Header {
+    name: Option<String>,
+    format: DumpFormat,
+    flags: u32,
+}
+
+/// Reads the next header, returns `None` if the end of the dump is reached.
+fn read_header<R: BufRead>(reader: &mut R, line_number: &mut usize) -> Result<Option<Header>> {
+    let mut header = Header { name: None, format: DumpFormat::ByteValue, flags: 0 };
+    let mut line = String::new();
+    let mut first = true;
+
+    loop {
+        line.clear();
+        *line_number += 1;
+        if reader.read_line(&mut line)? == 0 {
+            return if first { Ok(None) } else { Err(invalid_dump(*line_number, "unexpected end")) };
+        }
+        first = false;
+
+        let line = line.trim_end_matches('\n');
+        if line == "HEADER=END" {
+            return Ok(Some(header));
+        }
+
+        let (key, value) = match line.find('=') {
+            Some(pos) => (&line[..pos], &line[pos + 1..]),
+            None => return Err(invalid_dump(*line_number, "expected a key=value header")),
+        };
+
+        match key {
+            "VERSION" => match value.parse::<u32>() {
+                Ok(version) if version <= DUMP_VERSION => (),
+                _ => return Err(invalid_dump(*line_number, "unsupported version")),
+            },
+            "format" if value == "bytevalue" => header.format = DumpFormat::ByteValue,
+            "format" if value == "print" => header.format = DumpFormat::Print,
+            "format" => return Err(invalid_dump(*line_number, "unsupported format")),
+            "type" if value != "btree" => return Err(invalid_dump(*line_number, "unsupported type")),
+            "database" => header.name = Some(value.to_string()),
+            "duplicates" if value == "1" => header.flags |= ffi::MDB_DUPSORT,
+            _ => {
+                let flag = DB_FLAGS.iter().find(|(_, name)| *name == key);
+                if let (Some((flag, _)), "1") = (flag, value) {
+                    header.flags |= flag;
+                }
+            }
+        }
+    }
+}
+
+/// Loads the databases of a dump written in the `mdb_dump` format.
+pub(crate) fn load<R: BufRead>(env: &Env, mut reader: R) -> Result<()> {
+    let wtxn = env.write_txn()?;
+    let mut line_number = 0;
+    let mut line = String::new();
+
+    while let Some(header) = read_header(&mut reader, &mut line_number)? {
+        let name = header.name.as_deref();
+        let dbi = open_dbi(&wtxn, name, header.flags | ffi::MDB_CREATE)?
+            .ok_or(Error::Mdb(MdbError::NotFound))?;
+        if dbi_flags(&wtxn, dbi)? != header.flags {
+            return Err(Error::InvalidDatabaseFlags);
+        }
+
+        let mut key = None;
+        loop {
+            line.clear();
+            line_number += 1;
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid_dump(line_number, "unexpected end"));
+            }
+
+            let line = line.trim_end_matches('\n');
+            if line == "DATA=END" {
+                break;
+            }
+
+            let bytes = read_bytes(line, header.format)
+                .ok_or_else(|| invalid_dump(line_number, "invalid key or value"))?;
+
+            match key.take() {
+                None => key = Some(bytes),
+                Some(key) => {
+                    let mut key_val = unsafe { crate::into_val(&key) };
+                    let mut data_val = unsafe { crate::into_val(&bytes) };
+                    unsafe {
+                        mdb_result(ffi::mdb_put(wtxn.txn.txn, dbi, &mut key_val, &mut data_val, 0))?
+                    }
+                }
+            }
+        }
+
+        if key.is_some() {
+            return Err(invalid_dump(line_number, "a key has no value"));
+        }
+    }
+
+    wtxn.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_roundtrip() {
+        let bytes = b"hello\\ world\n\x00\xff";
+
+        for &format in &[DumpFormat::ByteValue, DumpFormat::Print] {
+            let mut line = Vec::new();
+            write_bytes(&mut line, bytes, format).unwrap();
+            let line = String::from_utf8(line).unwrap();
+            assert_eq!(read_bytes(line.trim_end_matches('\n'), format).unwrap(), bytes);
+        }
+
+        let mut line = Vec::new();
+        write_bytes(&mut line, bytes, DumpFormat::Print).unwrap();
+        assert_eq!(line, b" hello\\\\ world\\0a\\00\\ff\n");
+    }
+}
@@ -1,11 +1,15 @@
 use std::any::TypeId;
+use std::cmp::Ordering;
 use std::collections::hash_map::{Entry, HashMap};
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+use std::ffi::CStr;
 use std::ffi::CString;
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{self, AtomicUsize};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use std::{io, ptr, sync};
+use std::{io, marker, mem, panic, process, ptr, sync};
 #[cfg(windows)]
 use std::ffi::OsStr;
 #[cfg(unix)]
@@ -14,9 +18,16 @@ use std::os::unix::ffi::OsStrExt;
 use once_cell::sync::Lazy;
 use synchronoise::event::SignalEvent;
 
+use crate::dump::{self, DumpFormat};
 use crate::flags::Flags;
+use crate::flusher::{sync_env, Flusher};
+use crate::metadata::{self, Fingerprint};
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+use crate::restore::restore_data_file;
 use crate::mdb::error::mdb_result;
-use crate::{Database, Error, Result, RoTxn, RwTxn};
+use crate::migrations::Migrations;
+use crate::types::ByteSlice;
+use crate::{CodecSchema, Comparator, Database, DefaultComparator, Error, MdbError, Result, RoCursor, RoTxn, RwCursor, RwTxn};
 use crate::mdb::ffi;
 
 /// The list of opened environments, the value is an optional environment, it is None
@@ -24,7 +35,31 @@ use crate::mdb::ffi;
 /// noone tries to open the same environment between these two phases.
 ///
 /// Trying to open a None marked environment returns an error to the user trying to open it.
-static OPENED_ENV: Lazy<RwLock<HashMap<PathBuf, (Option<Env>, Arc<SignalEvent>)>>> = Lazy::new(RwLock::default);
+static OPENED_ENV: Lazy<RwLock<HashMap<PathBuf, EnvEntry>>> = Lazy::new(RwLock::default);
+
+type EnvEntry = (Option<Env>, Arc<SignalEvent>);
+
+/// The name, the key and data types, the flags and the comparators
+/// a database has been opened with.
+struct DbiEntry {
+    name: Option<String>,
+    types: (TypeId, TypeId),
+    flags: u32,
+    comparators: Comparators,
+}
+
+/// The custom key and duplicate data comparison functions to set on a database.
+#[derive(Clone, Copy, Default)]
+struct Comparators {
+    key: Option<(TypeId, ffi::MDB_cmp_func)>,
+    dup: Option<(TypeId, ffi::MDB_cmp_func)>,
+}
+
+impl Comparators {
+    fn type_ids(&self) -> (Option<TypeId>, Option<TypeId>) {
+        (self.key.map(|(id, _)| id), self.dup.map(|(id, _)| id))
+    }
+}
 
 // Thanks to the mozilla/rkv project
 // Workaround the UNC path on Windows, see https://github.com/rust-lang/rust/issues/42869.
@@ -41,6 +76,26 @@ fn canonicalize_path(path: &Path) -> io::Result<PathBuf> {
     url.to_file_path().map_err(|_e| io::Error::new(io::ErrorKind::Other, "path canonicalization error"))
 }
 
+/// Canonicalizes the path of a data file that may not exist yet,
+/// by canonicalizing its parent directory.
+fn canonicalize_file_path(path: &Path) -> io::Result<PathBuf> {
+    if path.exists() {
+        return canonicalize_path(path);
+    }
+
+    match path.file_name() {
+        Some(name) => {
+            let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+            let parent = canonicalize_path(parent.unwrap_or_else(|| Path::new(".")))?;
+            Ok(parent.join(name))
+        }
+        None => {
+            let msg = format!("{} is not a valid data file path", path.display());
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+}
+
 #[cfg(windows)]
 /// Adding a 'missing' trait from windows OsStrExt
 trait OsStrExtLmdb {
@@ -69,17 +124,69 @@ fn get_file_fd(file: &File) -> std::os::unix::io::RawFd {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnvOpenOptions {
     map_size: Option<usize>,
+    map_growth: Option<(MapGrowth, usize)>,
     max_readers: Option<u32>,
     max_dbs: Option<u32>,
+    read_txn_pool: Option<usize>,
+    create_dirs: bool,
+    durability: Durability,
+    codec_fingerprints: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    migrations: Option<Migrations>,
     flags: u32, // LMDB flags
 }
 
+/// How and when the committed transactions are flushed to disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Durability {
+    /// The data and the metadata are flushed at each commit, this is the default.
+    Full,
+    /// The metadata is flushed by the next commit (`MDB_NOMETASYNC`), a system crash
+    /// may undo the last committed transaction but the integrity is maintained.
+    MetaAsync,
+    /// Nothing is flushed at commit (`MDB_NOSYNC`), a background thread flushes the
+    /// environment every `interval`, or earlier once `max_unsynced_commits` write
+    /// transactions have been committed since the last flush. A system crash may lose
+    /// the transactions committed since the last flush, or corrupt the database if the
+    /// filesystem doesn't preserve the order of the writes.
+    Periodic {
+        interval: Duration,
+        max_unsynced_commits: Option<usize>,
+    },
+    /// Nothing is flushed at commit (`MDB_NOSYNC`), the application
+    /// calls [`Env::force_sync`] when it needs durability.
+    Manual,
+}
+
+/// How the memory map of an environment grows when [`Env::grow_map`] is called.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MapGrowth {
+    /// Multiplies the current map size by the given factor, which must be greater than one.
+    Factor(f64),
+    /// Adds the given number of bytes to the current map size.
+    Step(usize),
+}
+
+impl Default for EnvOpenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EnvOpenOptions {
     pub fn new() -> EnvOpenOptions {
         EnvOpenOptions {
             map_size: None,
+            map_growth: None,
             max_readers: None,
             max_dbs: None,
+            read_txn_pool: None,
+            create_dirs: false,
+            durability: Durability::Full,
+            codec_fingerprints: false,
+            migrations: None,
             flags: 0,
         }
     }
@@ -89,6 +196,70 @@ impl EnvOpenOptions {
         self
     }
 
+    /// Allows the memory map to grow with the given policy, up to `max_size` bytes,
+    /// each time [`Env::grow_map`] is called, usually after a [`MdbError::MapFull`] error.
+    ///
+    /// The map sizes are rounded up to a multiple of the system page size.
+    ///
+    /// [`MdbError::MapFull`]: crate::MdbError::MapFull
+    pub fn map_growth(&mut self, growth: MapGrowth, max_size: usize) -> &mut Self {
+        self.map_growth = Some((growth, max_size));
+        self
+    }
+
+    /// Applies the pending migration steps when the environment is opened in read-write
+    /// mode, see the [`migrations`](crate::migrations) module.
+    pub fn migrations(&mut self, migrations: Migrations) -> &mut Self {
+        self.migrations = Some(migrations);
+        self
+    }
+
+    /// Stores a fingerprint of the codecs of the databases, their type names and the ids
+    /// provided by [`DatabaseOpenOptions::codec_schemas`], in the reserved metadata database.
+    ///
+    /// The fingerprint is stored when a database is created and is verified each time it
+    /// is opened or created, opening it with other codecs returns an
+    /// [`Error::InvalidDatabaseTyping`] error, even after the environment has been closed.
+    /// The type names depend on the paths of the codecs, moving a codec into another module
+    /// changes its fingerprint, see [`Env::clear_codec_fingerprint`].
+    ///
+    /// The type names are the ones of [`std::any::type_name`], which are not guaranteed to
+    /// be stable across compiler versions: upgrading the compiler may change the fingerprints,
+    /// they must then be cleared with [`Env::clear_codec_fingerprint`].
+    ///
+    /// The metadata database requires one of the [`EnvOpenOptions::max_dbs`] named databases.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = Path::new("target").join("codec-fingerprints.mdb");
+    /// # let _ = fs::remove_dir_all(&path);
+    /// fs::create_dir_all(&path)?;
+    /// let env = EnvOpenOptions::new().max_dbs(10).codec_fingerprints(true).open(&path)?;
+    /// let _: Database<Str, OwnedType<u64>> = env.create_database(Some("counters"))?;
+    ///
+    /// env.prepare_for_closing().wait();
+    /// let env = EnvOpenOptions::new().max_dbs(10).codec_fingerprints(true).open(&path)?;
+    ///
+    /// // The database has been created with other codecs.
+    /// let ret = env.open_database::<Str, OwnedType<u32>>(Some("counters"));
+    /// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseTyping)));
+    ///
+    /// let db: Option<Database<Str, OwnedType<u64>>> = env.open_database(Some("counters"))?;
+    /// assert!(db.is_some());
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Error::InvalidDatabaseTyping`]: crate::Error::InvalidDatabaseTyping
+    pub fn codec_fingerprints(&mut self, enabled: bool) -> &mut Self {
+        self.codec_fingerprints = enabled;
+        self
+    }
+
     pub fn max_readers(&mut self, readers: u32) -> &mut Self {
         self.max_readers = Some(readers);
         self
@@ -99,6 +270,198 @@ impl EnvOpenOptions {
         self
     }
 
+    /// Defines when the committed transactions are flushed to disk, see [`Durability`].
+    ///
+    /// The background flusher of the [`Durability::Periodic`] policy is stopped, and
+    /// the environment flushed a last time, by [`Env::prepare_for_closing`]. Its interval
+    /// must be greater than zero, opening the environment returns an error otherwise.
+    /// No flusher is started for the environments opened in read-only mode.
+    ///
+    /// It replaces the [`no_sync`] and [`no_meta_sync`] flags set before, the last
+    /// of these calls wins.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    /// use heed::{Database, Durability, EnvFlag, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("durability.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .durability(Durability::Periodic {
+    ///         interval: Duration::from_millis(100),
+    ///         max_unsynced_commits: Some(1000),
+    ///     })
+    ///     .open(Path::new("target").join("durability.mdb"))?;
+    /// assert!(env.flags()?.contains(EnvFlag::NoSync));
+    ///
+    /// let db: Database<OwnedType<u32>, Unit> = env.create_database(None)?;
+    /// for i in 0..2000 {
+    ///     env.write(|wtxn| db.put(wtxn, &i, &()))?;
+    /// }
+    ///
+    /// env.prepare_for_closing().wait();
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`no_sync`]: EnvOpenOptions::no_sync
+    /// [`no_meta_sync`]: EnvOpenOptions::no_meta_sync
+    pub fn durability(&mut self, durability: Durability) -> &mut Self {
+        self.durability = durability;
+        self.flags &= !(Flags::MdbNoSync as u32 | Flags::MdbNoMetaSync as u32);
+        self
+    }
+
+    /// Doesn't flush the system buffers to disk when committing a transaction (`MDB_NOSYNC`).
+    ///
+    /// This optimization means a system crash can corrupt the database or lose the last
+    /// transactions if the buffers are not yet flushed to disk. The risk depends on the
+    /// filesystem preserving the order of the writes or not. An application crash doesn't
+    /// lose anything. Use [`Env::force_sync`] to flush the buffers when it fits the application.
+    ///
+    /// It replaces the [`durability`] policy set before with [`Durability::Full`].
+    ///
+    /// [`durability`]: EnvOpenOptions::durability
+    pub fn no_sync(&mut self, enabled: bool) -> &mut Self {
+        self.durability = Durability::Full;
+        self.set_env_flag(Flags::MdbNoSync, enabled)
+    }
+
+    /// Flushes the system buffers to disk only once per transaction, omitting the metadata
+    /// flush (`MDB_NOMETASYNC`).
+    ///
+    /// A system crash may undo the last committed transaction, the database integrity
+    /// is maintained. The metadata is flushed by the next commit or by [`Env::force_sync`].
+    ///
+    /// It replaces the [`durability`] policy set before with [`Durability::Full`].
+    ///
+    /// [`durability`]: EnvOpenOptions::durability
+    pub fn no_meta_sync(&mut self, enabled: bool) -> &mut Self {
+        self.durability = Durability::Full;
+        self.set_env_flag(Flags::MdbNoMetaSync, enabled)
+    }
+
+    /// Uses a writeable memory map (`MDB_WRITEMAP`), which is faster and uses fewer mallocs
+    /// but loses the protection of a read-only map: stray writes through a bad pointer
+    /// can silently corrupt the database. The data file may grow to the full map size
+    /// on some systems.
+    pub fn write_map(&mut self, enabled: bool) -> &mut Self {
+        self.set_env_flag(Flags::MdbWriteMap, enabled)
+    }
+
+    /// Flushes the memory map asynchronously when used with [`write_map`] (`MDB_MAPASYNC`).
+    ///
+    /// Like [`no_sync`], a system crash can lose the last transactions, an application
+    /// crash doesn't. [`Env::force_sync`] flushes the map synchronously.
+    ///
+    /// [`write_map`]: EnvOpenOptions::write_map
+    /// [`no_sync`]: EnvOpenOptions::no_sync
+    pub fn map_async(&mut self, enabled: bool) -> &mut Self {
+        self.set_env_flag(Flags::MdbMapAsync, enabled)
+    }
+
+    /// Turns off the readahead of the operating system (`MDB_NORDAHEAD`), it can improve
+    /// random read performance when the database is larger than the RAM, it has no
+    /// effect on durability.
+    pub fn no_read_ahead(&mut self, enabled: bool) -> &mut Self {
+        self.set_env_flag(Flags::MdbNoRdAhead, enabled)
+    }
+
+    /// Doesn't initialize the malloc'ed memory before writing it to disk (`MDB_NOMEMINIT`).
+    ///
+    /// It improves performance but unused parts of the pages may contain leftover
+    /// data from the memory of the process. It has no effect with [`write_map`].
+    ///
+    /// [`write_map`]: EnvOpenOptions::write_map
+    pub fn no_mem_init(&mut self, enabled: bool) -> &mut Self {
+        self.set_env_flag(Flags::MdbNoMemInit, enabled)
+    }
+
+    fn set_env_flag(&mut self, flag: Flags, enabled: bool) -> &mut Self {
+        if enabled {
+            self.flags |= flag as u32;
+        } else {
+            self.flags &= !(flag as u32);
+        }
+        self
+    }
+
+    /// Stores the environment in a single data file at the given path, along with a lock
+    /// file with the `-lock` suffix, instead of a directory containing a `data.mdb` and
+    /// a `lock.mdb` file (`MDB_NOSUBDIR`).
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = Path::new("target").join("single-file").join("foo.mdb");
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .single_file(true)
+    ///     .create_dirs(true)
+    ///     .open(&path)?;
+    ///
+    /// let db: Database<Str, Str> = env.create_database(None)?;
+    /// env.write(|wtxn| db.put(wtxn, &"hello", &"world"))?;
+    ///
+    /// assert!(path.is_file());
+    /// assert!(Path::new("target").join("single-file").join("foo.mdb-lock").is_file());
+    /// assert_eq!(env.path(), path.canonicalize()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn single_file(&mut self, enabled: bool) -> &mut Self {
+        self.set_env_flag(Flags::MdbNoSubDir, enabled)
+    }
+
+    /// Creates the directory of the environment, or the parent directory of the data file
+    /// when [`single_file`] is enabled, if it doesn't exist.
+    ///
+    /// [`single_file`]: EnvOpenOptions::single_file
+    pub fn create_dirs(&mut self, enabled: bool) -> &mut Self {
+        self.create_dirs = enabled;
+        self
+    }
+
+    /// Keeps up to `max_size` dropped read transactions in a pool, they are reset and
+    /// renewed by [`Env::read_txn`] instead of being aborted and begun again.
+    ///
+    /// The pooled transactions keep their reader slots, the pool must be smaller than
+    /// the maximum number of readers. The pool is shared between threads, enabling it
+    /// sets the `MDB_NOTLS` flag.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("read-txn-pool.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .read_txn_pool(8)
+    ///     .open(Path::new("target").join("read-txn-pool.mdb"))?;
+    /// let db: Database<Str, OwnedType<u32>> = env.create_database(None)?;
+    ///
+    /// for i in 0..100 {
+    ///     env.write(|wtxn| db.put(wtxn, &"counter", &i))?;
+    ///     // This read transaction is renewed from the pool.
+    ///     let counter = env.read(|rtxn| db.get(rtxn, &"counter"))?;
+    ///     assert_eq!(counter, Some(i));
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn read_txn_pool(&mut self, max_size: usize) -> &mut Self {
+        self.read_txn_pool = Some(max_size);
+        self
+    }
+
     /// Set one or [more LMDB flags](http://www.lmdb.tech/doc/group__mdb__env.html).
     ///
     /// ```
@@ -142,18 +505,91 @@ impl EnvOpenOptions {
     /// assert_eq!(ret, Some(5));
     /// # Ok(()) }
     /// ```
+    ///
+    /// # Safety
+    ///
+    /// It is unsafe to use unsafe LMDB flags such as `NO_SYNC`, `NO_META_SYNC`, or `NO_LOCK`.
     pub unsafe fn flag(&mut self, flag: Flags) -> &mut Self {
-        self.flags = self.flags | flag as u32;
+        self.flags |= flag as u32;
         self
     }
 
     pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<Env> {
-        let path = canonicalize_path(path.as_ref())?;
+        let env = self.raw_open(path.as_ref(), false)?;
+        if let Some(migrations) = &self.migrations {
+            migrations.apply(&env)?;
+        }
+        Ok(env)
+    }
+
+    /// Opens the environment in read-only mode (`MDB_RDONLY`), the returned [`ReadOnlyEnv`]
+    /// only exposes the operations that don't write to the environment.
+    ///
+    /// If the environment is already opened in this process, the same environment is
+    /// returned. Opening it in read-write mode while it is opened in read-only mode
+    /// returns an error.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = Path::new("target").join("read-only.mdb");
+    /// fs::create_dir_all(&path)?;
+    ///
+    /// let env = EnvOpenOptions::new().open(&path)?;
+    /// let db: Database<Str, Str> = env.create_database(None)?;
+    /// env.write(|wtxn| db.put(wtxn, &"hello", &"world"))?;
+    /// env.prepare_for_closing().wait();
+    ///
+    /// let env = EnvOpenOptions::new().open_read_only(&path)?;
+    /// let db: Database<Str, Str> = env.open_database(None)?.unwrap();
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// assert_eq!(db.get(&rtxn, &"hello")?, Some("world"));
+    /// drop(rtxn);
+    ///
+    /// assert!(EnvOpenOptions::new().open(&path).is_err());
+    /// # Ok(()) }
+    /// ```
+    pub fn open_read_only<P: AsRef<Path>>(&self, path: P) -> Result<ReadOnlyEnv> {
+        self.raw_open(path.as_ref(), true).map(ReadOnlyEnv)
+    }
+
+    fn raw_open(&self, path: &Path, read_only: bool) -> Result<Env> {
+        let single_file = self.flags & Flags::MdbNoSubDir as u32 != 0;
+
+        if let Durability::Periodic { interval: Duration::ZERO, .. } = self.durability {
+            let msg = "the interval of the periodic durability must be greater than zero";
+            return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+        }
+
+        if self.create_dirs {
+            let dir = if single_file { path.parent() } else { Some(path) };
+            if let Some(dir) = dir.filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)?;
+            }
+        }
+
+        let path = if single_file {
+            canonicalize_file_path(path)?
+        } else {
+            canonicalize_path(path)?
+        };
 
         let mut lock = OPENED_ENV.write().unwrap();
 
         match lock.entry(path) {
-            Entry::Occupied(entry) => entry.get().0.clone().ok_or(Error::DatabaseClosing),
+            Entry::Occupied(entry) => match entry.get().0.clone() {
+                Some(env) if !read_only && env.raw_flags()? & ffi::MDB_RDONLY != 0 => {
+                    let msg = "the environment is already opened in read-only mode";
+                    Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)))
+                }
+                Some(env) => Ok(env),
+                None => Err(Error::DatabaseClosing),
+            },
             Entry::Vacant(entry) => {
                 let path = entry.key();
                 let path_str = CString::new(path.as_os_str().as_bytes()).unwrap();
@@ -173,6 +609,26 @@ impl EnvOpenOptions {
                         mdb_result(ffi::mdb_env_set_mapsize(env, size))?;
                     }
 
+                    if let Some((growth, max_size)) = self.map_growth {
+                        let msg = match growth {
+                            MapGrowth::Factor(factor) if factor.partial_cmp(&1.0) != Some(Ordering::Greater) => {
+                                Some("map growth factor must be greater than one".to_string())
+                            }
+                            MapGrowth::Step(0) => {
+                                Some("map growth step must be greater than zero".to_string())
+                            }
+                            _ if max_size % page_size::get() != 0 => Some(format!(
+                                "maximum map size ({}) must be a multiple of the system page size ({})",
+                                max_size, page_size::get()
+                            )),
+                            _ => None,
+                        };
+                        if let Some(msg) = msg {
+                            ffi::mdb_env_close(env);
+                            return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+                        }
+                    }
+
                     if let Some(readers) = self.max_readers {
                         mdb_result(ffi::mdb_env_set_maxreaders(env, readers))?;
                     }
@@ -184,12 +640,24 @@ impl EnvOpenOptions {
                     // When the `sync-read-txn` feature is enabled, we must force LMDB
                     // to avoid using the thread local storage, this way we allow users
                     // to use references of RoTxn between threads safely.
-                    let flags = if cfg!(feature = "sync-read-txn") {
+                    // The same goes for the pool of read transactions that are
+                    // reused by different threads.
+                    let flags = if cfg!(feature = "sync-read-txn") || self.read_txn_pool.is_some() {
                         self.flags | Flags::MdbNoTls as u32
                     } else {
                         self.flags
                     };
 
+                    let flags = if read_only { flags | ffi::MDB_RDONLY } else { flags };
+
+                    let flags = match self.durability {
+                        Durability::Full => flags,
+                        Durability::MetaAsync => flags | Flags::MdbNoMetaSync as u32,
+                        Durability::Periodic { .. } | Durability::Manual => {
+                            flags | Flags::MdbNoSync as u32
+                        }
+                    };
+
                     let result = mdb_result(ffi::mdb_env_open(
                         env,
                         path_str.as_ptr(),
@@ -197,12 +665,32 @@ impl EnvOpenOptions {
                         0o600,
                     ));
 
-                    match result {
-                        Ok(()) => {
+                    // There is nothing to flush in a read-only environment.
+                    let read_only = flags & ffi::MDB_RDONLY != 0;
+                    let flusher = match (result, self.durability) {
+                        (Ok(()), Durability::Periodic { interval, max_unsynced_commits }) if !read_only => {
+                            Flusher::spawn(env, interval, max_unsynced_commits)
+                                .map(Some)
+                                .map_err(Error::Io)
+                        }
+                        (result, _) => result.map(|()| None).map_err(Error::from),
+                    };
+
+                    match flusher {
+                        Ok(flusher) => {
                             let signal_event = Arc::new(SignalEvent::manual(false));
                             let inner = EnvInner {
                                 env,
                                 dbi_open_mutex: sync::Mutex::default(),
+                                active_txns: AtomicUsize::new(0),
+                                resize_lock: RwLock::new(()),
+                                map_growth: self.map_growth,
+                                codec_fingerprints: self.codec_fingerprints,
+                                read_txn_pool: self.read_txn_pool.map(|max_size| ReadTxnPool {
+                                    max_size,
+                                    txns: sync::Mutex::default(),
+                                }),
+                                flusher,
                                 path: path.clone(),
                             };
                             let env = Env(Arc::new(inner));
@@ -211,7 +699,7 @@ impl EnvOpenOptions {
                         }
                         Err(e) => {
                             ffi::mdb_env_close(env);
-                            Err(e.into())
+                            Err(e)
                         }
                     }
                 }
@@ -231,10 +719,24 @@ pub struct Env(Arc<EnvInner>);
 
 struct EnvInner {
     env: *mut ffi::MDB_env,
-    dbi_open_mutex: sync::Mutex<HashMap<u32, (TypeId, TypeId)>>,
+    dbi_open_mutex: sync::Mutex<HashMap<u32, DbiEntry>>,
+    /// The number of transactions currently alive in this process.
+    active_txns: AtomicUsize,
+    /// Taken exclusively while resizing, new transactions wait for the resize to be done.
+    resize_lock: RwLock<()>,
+    map_growth: Option<(MapGrowth, usize)>,
+    codec_fingerprints: bool,
+    read_txn_pool: Option<ReadTxnPool>,
+    flusher: Option<Flusher>,
     path: PathBuf,
 }
 
+/// The reset read transactions that can be renewed instead of begun.
+struct ReadTxnPool {
+    max_size: usize,
+    txns: sync::Mutex<Vec<*mut ffi::MDB_txn>>,
+}
+
 unsafe impl Send for EnvInner {}
 
 unsafe impl Sync for EnvInner {}
@@ -246,7 +748,13 @@ impl Drop for EnvInner {
         match lock.remove(&self.path) {
             None => panic!("It seems another env closed this env before"),
             Some((_, signal_event)) => {
-                unsafe { let _ = ffi::mdb_env_close(self.env); }
+                self.stop_flusher();
+                if let Some(pool) = &self.read_txn_pool {
+                    for txn in pool.txns.lock().unwrap().drain(..) {
+                        unsafe { ffi::mdb_txn_abort(txn); }
+                    }
+                }
+                unsafe { ffi::mdb_env_close(self.env); }
                 // We signal to all the waiters that we have closed the env.
                 signal_event.signal();
             }
@@ -254,38 +762,300 @@ impl Drop for EnvInner {
     }
 }
 
+impl EnvInner {
+    /// Stops the background flusher, if any, and flushes the environment a last time.
+    fn stop_flusher(&self) {
+        if let Some(flusher) = &self.flusher {
+            flusher.stop();
+            let _ = sync_env(self.env);
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum CompactionOption {
     Enabled,
     Disabled,
 }
 
+/// The statistics of a database B-tree, or of the main one of an environment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DatabaseStat {
+    /// Size of a database page, this is the same for all the databases of an environment.
+    pub page_size: u32,
+    /// Depth (height) of the B-tree.
+    pub depth: u32,
+    /// Number of internal (non-leaf) pages.
+    pub branch_pages: u64,
+    /// Number of leaf pages.
+    pub leaf_pages: u64,
+    /// Number of overflow pages, used by the values that don't fit in a single page.
+    pub overflow_pages: u64,
+    /// Number of data items.
+    pub entries: u64,
+}
+
+impl DatabaseStat {
+    pub(crate) fn from_raw(stat: ffi::MDB_Stat) -> DatabaseStat {
+        DatabaseStat {
+            page_size: stat.ms_psize,
+            depth: stat.ms_depth,
+            branch_pages: stat.ms_branch_pages as u64,
+            leaf_pages: stat.ms_leaf_pages as u64,
+            overflow_pages: stat.ms_overflow_pages as u64,
+            entries: stat.ms_entries as u64,
+        }
+    }
+}
+
+/// A backend-agnostic environment flag, see [`Env::flags`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EnvFlag {
+    FixedMap,
+    NoSubDir,
+    NoSync,
+    ReadOnly,
+    NoMetaSync,
+    WriteMap,
+    MapAsync,
+    NoTls,
+    NoLock,
+    NoReadAhead,
+    NoMemInit,
+}
+
+impl EnvFlag {
+    /// Returns `true` if the storage backend heed is compiled with supports this flag.
+    pub fn is_supported(self) -> bool {
+        self.bits().is_some()
+    }
+
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    fn bits(self) -> Option<u32> {
+        let flag = match self {
+            EnvFlag::FixedMap => Flags::MdbFixedmap,
+            EnvFlag::NoSubDir => Flags::MdbNoSubDir,
+            EnvFlag::NoSync => Flags::MdbNoSync,
+            EnvFlag::ReadOnly => Flags::MdbRdOnly,
+            EnvFlag::NoMetaSync => Flags::MdbNoMetaSync,
+            EnvFlag::WriteMap => Flags::MdbWriteMap,
+            EnvFlag::MapAsync => Flags::MdbMapAsync,
+            EnvFlag::NoTls => Flags::MdbNoTls,
+            EnvFlag::NoLock => Flags::MdbNoLock,
+            EnvFlag::NoReadAhead => Flags::MdbNoRdAhead,
+            EnvFlag::NoMemInit => Flags::MdbNoMemInit,
+        };
+        Some(flag as u32)
+    }
+
+    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+    fn bits(self) -> Option<u32> {
+        let flag = match self {
+            EnvFlag::FixedMap | EnvFlag::NoLock => return None,
+            EnvFlag::NoSubDir => Flags::MdbNoSubDir,
+            EnvFlag::NoSync => Flags::MdbNoSync,
+            EnvFlag::ReadOnly => Flags::MdbRdOnly,
+            EnvFlag::NoMetaSync => Flags::MdbNoMetaSync,
+            EnvFlag::WriteMap => Flags::MdbWriteMap,
+            EnvFlag::MapAsync => Flags::MdbMapAsync,
+            EnvFlag::NoTls => Flags::MdbNoTls,
+            EnvFlag::NoReadAhead => Flags::MdbNoRdAhead,
+            EnvFlag::NoMemInit => Flags::MdbNoMemInit,
+        };
+        Some(flag as u32)
+    }
+}
+
+/// The effective flags of an opened environment, returned by [`Env::flags`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EnvFlags(u32);
+
+impl EnvFlags {
+    /// Returns `true` if the environment has been opened with this flag.
+    pub fn contains(&self, flag: EnvFlag) -> bool {
+        matches!(flag.bits(), Some(bits) if self.0 & bits == bits)
+    }
+
+    /// Returns the raw flags of the storage backend.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Information about an environment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EnvInfo {
+    /// Size of the data memory map, in bytes.
+    pub map_size: usize,
+    /// Number of the last used page, the used size of the map is
+    /// `(last_page_number + 1) * page_size` bytes.
+    pub last_page_number: usize,
+    /// Id of the last committed transaction.
+    pub last_txn_id: usize,
+    /// Maximum number of reader slots in the environment.
+    pub max_readers: u32,
+    /// Number of reader slots used in the environment.
+    pub num_readers: u32,
+}
+
+/// A reader slot of the lock file of an environment, as listed by [`Env::readers`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReaderInfo {
+    /// The id of the process that owns the slot.
+    pub pid: u32,
+    /// The id of the thread that owns the slot.
+    pub thread: u64,
+    /// The id of the snapshot the reader is using, `None` if the read transaction has been reset.
+    pub txn_id: Option<u64>,
+}
+
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+impl ReaderInfo {
+    /// Parses a line of `mdb_reader_list`, e.g. `     12345 7f1c2b3a4700 42`.
+    fn from_line(line: &str) -> Option<ReaderInfo> {
+        let mut parts = line.split_whitespace();
+        let pid = parts.next()?.parse().ok()?;
+        let thread = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let txn_id = match parts.next()? {
+            "-" => None,
+            txn_id => Some(txn_id.parse().ok()?),
+        };
+        Some(ReaderInfo { pid, thread, txn_id })
+    }
+}
+
+impl EnvInfo {
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    fn from_raw(info: ffi::MDB_envinfo) -> EnvInfo {
+        EnvInfo {
+            map_size: info.me_mapsize,
+            last_page_number: info.me_last_pgno,
+            last_txn_id: info.me_last_txnid,
+            max_readers: info.me_maxreaders,
+            num_readers: info.me_numreaders,
+        }
+    }
+
+    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+    fn from_raw(info: ffi::MDB_envinfo) -> EnvInfo {
+        EnvInfo {
+            map_size: info.mi_mapsize as usize,
+            last_page_number: info.mi_last_pgno as usize,
+            last_txn_id: info.mi_recent_txnid as usize,
+            max_readers: info.mi_maxreaders,
+            num_readers: info.mi_numreaders,
+        }
+    }
+}
+
 impl Env {
     pub(crate) fn env_mut_ptr(&self) -> *mut ffi::MDB_env {
         self.0.env
     }
 
+    /// Returns the fingerprint of the given codecs, if the fingerprints are enabled.
+    fn fingerprint<KC, DC>(&self, schema_ids: (u32, u32)) -> Option<Fingerprint> {
+        if self.0.codec_fingerprints {
+            Some(Fingerprint::of::<KC, DC>(schema_ids))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name the database with the given handle has been opened
+    /// with, `None` for the unnamed database.
+    fn registered_name(&self, dbi: u32) -> Option<String> {
+        self.0.dbi_open_mutex.lock().unwrap().get(&dbi).and_then(|entry| entry.name.clone())
+    }
+
+    /// Retrieves the flags this environment has been opened with.
+    fn raw_flags(&self) -> Result<u32> {
+        let mut flags = 0;
+        unsafe { mdb_result(ffi::mdb_env_get_flags(self.0.env, &mut flags))? };
+        Ok(flags)
+    }
+
+    pub(crate) fn txn_started(&self) {
+        let _resize = self.0.resize_lock.read().unwrap();
+        self.0.active_txns.fetch_add(1, atomic::Ordering::SeqCst);
+    }
+
+    pub(crate) fn txn_ended(&self) {
+        self.0.active_txns.fetch_sub(1, atomic::Ordering::SeqCst);
+    }
+
+    pub(crate) fn write_txn_committed(&self) {
+        if let Some(flusher) = &self.0.flusher {
+            flusher.commit_done();
+        }
+    }
+
+    pub(crate) fn has_read_txn_pool(&self) -> bool {
+        self.0.read_txn_pool.is_some()
+    }
+
+    pub(crate) fn take_pooled_read_txn(&self) -> Option<*mut ffi::MDB_txn> {
+        self.0.read_txn_pool.as_ref().and_then(|pool| pool.txns.lock().unwrap().pop())
+    }
+
+    /// Puts a reset read transaction back in the pool, returns `false` if the pool is full.
+    pub(crate) fn give_back_pooled_read_txn(&self, txn: *mut ffi::MDB_txn) -> bool {
+        match &self.0.read_txn_pool {
+            Some(pool) => {
+                let mut txns = pool.txns.lock().unwrap();
+                if txns.len() < pool.max_size {
+                    txns.push(txn);
+                    true
+                } else {
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+
     pub fn open_database<KC, DC>(&self, name: Option<&str>) -> Result<Option<Database<KC, DC>>>
     where
         KC: 'static,
         DC: 'static,
     {
         let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
+        let fingerprint = self.fingerprint::<KC, DC>((0, 0));
         Ok(self
-            .raw_open_database(name, types)?
+            .raw_open_database(name, types, None, Comparators::default(), fingerprint)?
             .map(|db| Database::new(self.env_mut_ptr() as _, db)))
     }
 
+    /// Returns a [`DatabaseOpenOptions`] builder to open or create a database
+    /// with specific codecs and flags.
+    pub fn database_options(&self) -> DatabaseOpenOptions<'_, UntypedKey, UntypedData> {
+        DatabaseOpenOptions {
+            env: self,
+            name: None,
+            flags: 0,
+            comparators: Comparators::default(),
+            schema_ids: (0, 0),
+            _phantom: marker::PhantomData,
+        }
+    }
+
     fn raw_open_database(
         &self,
         name: Option<&str>,
         types: (TypeId, TypeId),
+        flags: Option<u32>,
+        comparators: Comparators,
+        fingerprint: Option<Fingerprint>,
     ) -> Result<Option<u32>> {
         let rtxn = self.read_txn()?;
 
+        if let Some(fingerprint) = fingerprint {
+            metadata::check_fingerprint(&rtxn, name, fingerprint)?;
+        }
+
         let mut dbi = 0;
-        let name = name.map(|n| CString::new(n).unwrap());
-        let name_ptr = match name {
+        let c_name = name.map(|n| CString::new(n).unwrap());
+        let name_ptr = match c_name {
             Some(ref name) => name.as_bytes_with_nul().as_ptr() as *const _,
             None => ptr::null(),
         };
@@ -294,19 +1064,17 @@ impl Env {
 
         let result = unsafe { mdb_result(ffi::mdb_dbi_open(rtxn.txn, name_ptr, 0, &mut dbi)) };
 
-        drop(name);
+        drop(c_name);
 
         match result {
             Ok(()) => {
+                let db_flags = dbi_flags(&rtxn, dbi)?;
+                check_dbi(&lock, dbi, types, db_flags, flags, comparators)?;
+                set_comparators(&rtxn, dbi, comparators)?;
                 rtxn.commit()?;
-
-                let old_types = lock.entry(dbi).or_insert(types);
-
-                if *old_types == types {
-                    Ok(Some(dbi))
-                } else {
-                    Err(Error::InvalidDatabaseTyping)
-                }
+                // An aborted transaction closes the handle, it is only registered once committed.
+                register_dbi(&mut lock, dbi, name, types, db_flags, comparators);
+                Ok(Some(dbi))
             }
             Err(e) if e.not_found() => Ok(None),
             Err(e) => Err(e.into()),
@@ -334,139 +1102,1557 @@ impl Env {
         DC: 'static,
     {
         let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
-        self.raw_create_database(name, types, parent_wtxn)
+        let fingerprint = self.fingerprint::<KC, DC>((0, 0));
+        self.raw_create_database(name, types, None, Comparators::default(), fingerprint, parent_wtxn)
             .map(|db| Database::new(self.env_mut_ptr() as _, db))
     }
 
-    fn raw_create_database(
-        &self,
-        name: Option<&str>,
-        types: (TypeId, TypeId),
+    /// Creates a database that can store multiple sorted values for a single key (`MDB_DUPSORT`).
+    ///
+    /// The values of such a database are sorted by their bytes representation, the
+    /// [`Database::get_duplicates`] and [`Database::delete_one`] methods can be used
+    /// to read and remove specific values of a key.
+    ///
+    /// If the database already exists without the `MDB_DUPSORT` flag,
+    /// an [`Error::InvalidDatabaseFlags`] error is returned.
+    ///
+    /// [`Error::InvalidDatabaseFlags`]: crate::Error::InvalidDatabaseFlags
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{EnvOpenOptions, Database};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("dup-database.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("dup-database.mdb"))?;
+    ///
+    /// let db: Database<Str, Str> = env.create_dup_database(Some("colors"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &"fruits", &"orange")?;
+    /// db.put(&mut wtxn, &"fruits", &"apple")?;
+    /// db.put(&mut wtxn, &"vegetables", &"carrot")?;
+    ///
+    /// let fruits: Vec<_> = db.get_duplicates(&wtxn, &"fruits")?.unwrap().collect::<Result<_, _>>()?;
+    /// assert_eq!(fruits, vec![("fruits", "apple"), ("fruits", "orange")]);
+    ///
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn create_dup_database<KC, DC>(&self, name: Option<&str>) -> Result<Database<KC, DC>>
+    where
+        KC: 'static,
+        DC: 'static,
+    {
+        let mut parent_wtxn = self.write_txn()?;
+        let db = self.create_dup_database_with_txn(name, &mut parent_wtxn)?;
+        parent_wtxn.commit()?;
+        Ok(db)
+    }
+
+    /// Creates a database that can store multiple sorted values for a single key (`MDB_DUPSORT`)
+    /// by using the given parent write transaction.
+    pub fn create_dup_database_with_txn<KC, DC>(
+        &self,
+        name: Option<&str>,
         parent_wtxn: &mut RwTxn,
+    ) -> Result<Database<KC, DC>>
+    where
+        KC: 'static,
+        DC: 'static,
+    {
+        let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
+        let flags = Some(ffi::MDB_DUPSORT);
+        let fingerprint = self.fingerprint::<KC, DC>((0, 0));
+        self.raw_create_database(name, types, flags, Comparators::default(), fingerprint, parent_wtxn)
+            .map(|db| Database::new(self.env_mut_ptr() as _, db))
+    }
+
+    /// Returns the names of the named databases of this environment,
+    /// by walking the keys of the unnamed database.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{EnvOpenOptions, Database};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("database-names.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("database-names.mdb"))?;
+    ///
+    /// let main: Database<Str, Str> = env.create_database(None)?;
+    /// let users: Database<Str, Str> = env.create_database(Some("users"))?;
+    /// env.create_database::<Str, Str>(Some("posts"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// // The keys of the unnamed database that are not databases are ignored.
+    /// main.put(&mut wtxn, &"not-a-database", &"hello")?;
+    /// assert_eq!(env.database_names(&wtxn)?, vec!["posts", "users"]);
+    ///
+    /// // A deleted database can be recreated with different types.
+    /// env.delete_database(&mut wtxn, users)?;
+    /// assert_eq!(env.database_names(&wtxn)?, vec!["posts"]);
+    /// wtxn.commit()?;
+    ///
+    /// let users: Database<OwnedType<u32>, Str> = env.create_database(Some("users"))?;
+    /// let rtxn = env.read_txn()?;
+    /// assert!(users.is_empty(&rtxn)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn database_names<T>(&self, rtxn: &RoTxn<T>) -> Result<Vec<String>> {
+        assert_eq!(self.env_mut_ptr(), rtxn.env.env_mut_ptr());
+
+        let main_dbi = open_dbi(rtxn, None, 0)?.ok_or(Error::Mdb(MdbError::NotFound))?;
+        let mut cursor = RoCursor::new(rtxn, main_dbi)?;
+        let mut names = Vec::new();
+
+        let mut entry = cursor.move_on_first()?;
+        while let Some((key, _)) = entry {
+            // The key comes from the memory map, the flags of its node tell if it names a database.
+            if unsafe { ffi::is_database_key(key) } {
+                names.push(String::from_utf8(key.to_vec()).unwrap());
+            }
+            entry = cursor.move_on_next()?;
+        }
+
+        Ok(names)
+    }
+
+    /// Deletes the given database from the environment and closes its handle,
+    /// it can then be recreated with different types or flags.
+    ///
+    /// The unnamed database cannot be deleted, it is only emptied.
+    ///
+    /// The other copies of this database handle must no longer be used,
+    /// neither in this transaction nor in the read transactions that are still alive.
+    ///
+    /// LMDB closes the handle right away, even if the transaction is aborted afterward:
+    /// the database is then kept but it must be opened again to get a new handle.
+    pub fn delete_database<KC, DC, T>(&self, wtxn: &mut RwTxn<T>, db: Database<KC, DC>) -> Result<()> {
+        assert_eq!(self.env_mut_ptr() as usize, db.env_ident);
+        assert_eq!(self.env_mut_ptr(), wtxn.txn.env.env_mut_ptr());
+
+        if let Some(name) = self.registered_name(db.dbi) {
+            metadata::remove_fingerprint(wtxn, Some(&name))?;
+        }
+
+        // The handle is closed by LMDB whether the transaction commits or not,
+        // it can then be reused for another database.
+        let mut lock = self.0.dbi_open_mutex.lock().unwrap();
+        unsafe { mdb_result(ffi::mdb_drop(wtxn.txn.txn, db.dbi, 1))? };
+        lock.remove(&db.dbi);
+
+        Ok(())
+    }
+
+    /// Removes the stored fingerprint of the codecs of the given database, the next time
+    /// the database is created it is stored again, see [`EnvOpenOptions::codec_fingerprints`].
+    ///
+    /// It must be called when the codecs of a database are deliberately changed,
+    /// for example by a migration step, returns `false` if there was no fingerprint.
+    pub fn clear_codec_fingerprint<T>(&self, wtxn: &mut RwTxn<T>, name: Option<&str>) -> Result<bool> {
+        assert_eq!(self.env_mut_ptr(), wtxn.txn.env.env_mut_ptr());
+        metadata::remove_fingerprint(wtxn, name)
+    }
+
+    /// Copies the entries of the given database into a new database with the given name,
+    /// created with the same flags and comparators, and returns it.
+    ///
+    /// The entries are appended in order to the new database, which must not already exist.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{EnvOpenOptions, Database};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("copy-database.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("copy-database.mdb"))?;
+    ///
+    /// let colors = env.database_options().types::<Str, Str>().name("colors").dup_sort(true).create()?;
+    /// # let palette = env.database_options().types::<Str, Str>().name("palette").dup_sort(true).open()?;
+    /// # let backup = env.database_options().types::<Str, Str>().name("backup").dup_sort(true).open()?;
+    /// let mut wtxn = env.write_txn()?;
+    /// # for db in palette.into_iter().chain(backup) {
+    /// #     env.delete_database(&mut wtxn, db)?;
+    /// # }
+    /// colors.clear(&mut wtxn)?;
+    /// colors.put(&mut wtxn, &"sky", &"blue")?;
+    /// colors.put(&mut wtxn, &"sky", &"grey")?;
+    /// colors.put(&mut wtxn, &"grass", &"green")?;
+    ///
+    /// let backup = env.copy_database(&mut wtxn, colors, "backup")?;
+    /// // The new name must not be used.
+    /// assert!(env.copy_database(&mut wtxn, colors, "backup").is_err());
+    ///
+    /// let palette = env.rename_database(&mut wtxn, colors, "palette")?;
+    /// wtxn.commit()?;
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// assert_eq!(env.database_names(&rtxn)?, vec!["backup", "palette"]);
+    /// for db in &[backup, palette] {
+    ///     let entries: Vec<_> = db.iter(&rtxn)?.collect::<Result<_, _>>()?;
+    ///     assert_eq!(entries, vec![("grass", "green"), ("sky", "blue"), ("sky", "grey")]);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn copy_database<KC, DC, T>(
+        &self,
+        wtxn: &mut RwTxn<T>,
+        src: Database<KC, DC>,
+        dst_name: &str,
+    ) -> Result<Database<KC, DC>>
+    where
+        KC: 'static,
+        DC: 'static,
+    {
+        assert_eq!(self.env_mut_ptr() as usize, src.env_ident);
+        assert_eq!(self.env_mut_ptr(), wtxn.txn.env.env_mut_ptr());
+
+        if open_dbi(wtxn, Some(dst_name), 0)?.is_some() {
+            let msg = format!("the {} database already exists", dst_name);
+            return Err(Error::Io(io::Error::new(io::ErrorKind::AlreadyExists, msg)));
+        }
+
+        let flags = dbi_flags(wtxn, src.dbi)?;
+        let comparators = match self.0.dbi_open_mutex.lock().unwrap().get(&src.dbi) {
+            Some(entry) => entry.comparators,
+            None => Comparators::default(),
+        };
+        let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
+        let dst = self.raw_create_database(Some(dst_name), types, Some(flags), comparators, None, wtxn)?;
+        if let Some(src_name) = self.registered_name(src.dbi) {
+            metadata::copy_fingerprint(wtxn, &src_name, dst_name)?;
+        }
+
+        let mut src_cursor = RoCursor::new(wtxn, src.dbi)?;
+        let mut dst_cursor = RwCursor::new(wtxn, dst)?;
+        let dup_sort = flags & ffi::MDB_DUPSORT != 0;
+
+        let mut entry = src_cursor.move_on_first()?;
+        while let Some((key, data)) = entry {
+            dst_cursor.append(key, data)?;
+            if dup_sort {
+                // The other data items of the key are appended as its duplicates.
+                while let Some((key, data)) = src_cursor.move_on_next_dup()? {
+                    dst_cursor.append_dup(key, data)?;
+                }
+                entry = src_cursor.move_on_next_nodup()?;
+            } else {
+                entry = src_cursor.move_on_next()?;
+            }
+        }
+
+        Ok(Database::new(self.env_mut_ptr() as _, dst))
+    }
+
+    /// Renames the given database, by copying its entries into a new database with
+    /// the given name and deleting it, see [`Env::copy_database`] and [`Env::delete_database`].
+    ///
+    /// The readers never see a partially renamed database, the changes are only
+    /// visible once the write transaction is committed.
+    pub fn rename_database<KC, DC, T>(
+        &self,
+        wtxn: &mut RwTxn<T>,
+        db: Database<KC, DC>,
+        new_name: &str,
+    ) -> Result<Database<KC, DC>>
+    where
+        KC: 'static,
+        DC: 'static,
+    {
+        let renamed = self.copy_database(wtxn, db, new_name)?;
+        self.delete_database(wtxn, db)?;
+        Ok(renamed)
+    }
+
+    fn raw_create_database<T>(
+        &self,
+        name: Option<&str>,
+        types: (TypeId, TypeId),
+        flags: Option<u32>,
+        comparators: Comparators,
+        fingerprint: Option<Fingerprint>,
+        parent_wtxn: &mut RwTxn<T>,
     ) -> Result<u32> {
-        let wtxn = self.nested_write_txn(parent_wtxn)?;
+        let mut wtxn = self.nested_write_txn(parent_wtxn)?;
+
+        if let Some(fingerprint) = fingerprint {
+            metadata::check_or_store_fingerprint(&mut wtxn, name, fingerprint)?;
+        }
 
         let mut dbi = 0;
-        let name = name.map(|n| CString::new(n).unwrap());
-        let name_ptr = match name {
+        let c_name = name.map(|n| CString::new(n).unwrap());
+        let name_ptr = match c_name {
             Some(ref name) => name.as_bytes_with_nul().as_ptr() as *const _,
             None => ptr::null(),
         };
 
-        let mut lock = self.0.dbi_open_mutex.lock().unwrap();
+        let mut lock = self.0.dbi_open_mutex.lock().unwrap();
+
+        let result = unsafe {
+            mdb_result(ffi::mdb_dbi_open(
+                wtxn.txn.txn,
+                name_ptr,
+                flags.unwrap_or(0) | ffi::MDB_CREATE,
+                &mut dbi,
+            ))
+        };
+
+        drop(c_name);
+
+        match result {
+            Ok(()) => {
+                let db_flags = dbi_flags(&wtxn, dbi)?;
+                check_dbi(&lock, dbi, types, db_flags, flags, comparators)?;
+                set_comparators(&wtxn, dbi, comparators)?;
+                wtxn.commit()?;
+                // An aborted transaction closes the handle, it is only registered once committed.
+                register_dbi(&mut lock, dbi, name, types, db_flags, comparators);
+                Ok(dbi)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn write_txn(&self) -> Result<RwTxn<'_, '_>> {
+        RwTxn::new(self)
+    }
+
+    pub fn typed_write_txn<T>(&self) -> Result<RwTxn<'_, '_, T>> {
+        RwTxn::<T>::new(self)
+    }
+
+    pub fn nested_write_txn<'e, 'p: 'e, T>(&'e self, parent: &'p mut RwTxn<T>) -> Result<RwTxn<'e, 'p, T>> {
+        RwTxn::nested(self, parent)
+    }
+
+    pub fn read_txn(&self) -> Result<RoTxn<'_>> {
+        RoTxn::new(self)
+    }
+
+    pub fn typed_read_txn<T>(&self) -> Result<RoTxn<'_, T>> {
+        RoTxn::new(self)
+    }
+
+    /// Runs the closure in a new write transaction, commits it if the closure
+    /// returns `Ok` and aborts it otherwise, returns the value of the closure.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("txn-closures.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(Path::new("target").join("txn-closures.mdb"))?;
+    /// let db: Database<Str, OwnedType<u32>> = env.create_database(None)?;
+    /// # env.write(|wtxn| db.clear(wtxn))?;
+    ///
+    /// let len = env.write(|wtxn| {
+    ///     db.put(wtxn, &"one", &1)?;
+    ///     db.put(wtxn, &"two", &2)?;
+    ///     db.len(wtxn)
+    /// })?;
+    /// assert_eq!(len, 2);
+    ///
+    /// // The transaction is aborted when the closure fails.
+    /// let ret: heed::Result<()> = env.write(|wtxn| {
+    ///     db.put(wtxn, &"three", &3)?;
+    ///     Err(heed::Error::DatabaseClosing)
+    /// });
+    /// assert!(ret.is_err());
+    ///
+    /// let three = env.read(|rtxn| db.get(rtxn, &"three"))?;
+    /// assert_eq!(three, None);
+    /// # Ok(()) }
+    /// ```
+    pub fn write<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut RwTxn) -> Result<T>,
+    {
+        let mut wtxn = self.write_txn()?;
+        let value = f(&mut wtxn)?;
+        wtxn.commit()?;
+        Ok(value)
+    }
+
+    /// Runs the closure in a new write transaction like [`Env::write`],
+    /// but retries it when the transaction fails because of the memory map.
+    ///
+    /// On a [`MdbError::MapFull`] error the map grows following the [`MapGrowth`] policy
+    /// of the environment, the error is returned if there is no policy or the maximum
+    /// size is reached. On a [`MdbError::MapResized`] error, the map size that another
+    /// process has set is adopted. No other transaction must be alive in this process.
+    ///
+    /// [`MdbError::MapFull`]: crate::MdbError::MapFull
+    /// [`MdbError::MapResized`]: crate::MdbError::MapResized
+    pub fn write_with_retry<F, T>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(&mut RwTxn) -> Result<T>,
+    {
+        loop {
+            match self.write(&mut f) {
+                Err(Error::Mdb(MdbError::MapFull)) => {
+                    if self.grow_map()?.is_none() {
+                        return Err(Error::Mdb(MdbError::MapFull));
+                    }
+                }
+                Err(e) if is_map_resized(&e) => self.resize(0)?,
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs the closure in a new read transaction and returns its value.
+    pub fn read<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&RoTxn) -> Result<T>,
+    {
+        let rtxn = self.read_txn()?;
+        f(&rtxn)
+    }
+
+    // TODO rename into `copy_to_file` for more clarity
+    pub fn copy_to_path<P: AsRef<Path>>(&self, path: P, option: CompactionOption) -> Result<File> {
+        let file = File::create(&path)?;
+        let fd = get_file_fd(&file);
+
+        unsafe { self.copy_to_fd(fd, option)?; }
+
+        // We reopen the file to make sure the cursor is at the start,
+        // even a seek to start doesn't work properly.
+        let file = File::open(path)?;
+
+        Ok(file)
+    }
+
+    /// Copies an LMDB environment to the specified file descriptor,
+    /// with an option for compaction.
+    ///
+    /// # Safety
+    ///
+    /// The file descriptor must be valid and opened in write mode.
+    pub unsafe fn copy_to_fd(&self, fd: ffi::mdb_filehandle_t, option: CompactionOption) -> Result<()> {
+        let flags = if let CompactionOption::Enabled = option { ffi::MDB_CP_COMPACT } else { 0 };
+
+        // The copy reads the map in its own read transaction, the map must not be resized meanwhile.
+        self.txn_started();
+        let result = mdb_result(ffi::mdb_env_copy2fd(self.0.env, fd, flags));
+        self.txn_ended();
+        result?;
+
+        Ok(())
+    }
+
+    /// Streams a consistent snapshot of the environment into the given writer,
+    /// with an option for compaction, returns the number of bytes written.
+    ///
+    /// The snapshot is the content of a `data.mdb` file, it is copied through a pipe
+    /// by a background thread while the writer is fed by the current one.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{CompactionOption, Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("copy-to-writer.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(Path::new("target").join("copy-to-writer.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(None)?;
+    /// env.write(|wtxn| db.put(wtxn, &"hello", &"world"))?;
+    ///
+    /// let mut snapshot = Vec::new();
+    /// let written = env.copy_to_writer(&mut snapshot, CompactionOption::Enabled)?;
+    /// assert_eq!(written, snapshot.len() as u64);
+    ///
+    /// let copy_path = Path::new("target").join("copy-to-writer-copy.mdb");
+    /// fs::create_dir_all(&copy_path)?;
+    /// fs::write(copy_path.join("data.mdb"), &snapshot)?;
+    ///
+    /// let copy = EnvOpenOptions::new().open(&copy_path)?;
+    /// let db: Database<Str, Str> = copy.open_database(None)?.unwrap();
+    /// let rtxn = copy.read_txn()?;
+    /// assert_eq!(db.get(&rtxn, &"hello")?, Some("world"));
+    /// # Ok(()) }
+    /// ```
+    #[cfg(unix)]
+    pub fn copy_to_writer<W: io::Write>(&self, mut writer: W, option: CompactionOption) -> Result<u64> {
+        use std::os::unix::io::FromRawFd;
+        use std::thread;
+
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
+        }
+
+        // The files close the pipe ends when dropped.
+        let mut reader = unsafe { File::from_raw_fd(fds[0]) };
+        let pipe_writer = unsafe { File::from_raw_fd(fds[1]) };
+
+        let flags = if let CompactionOption::Enabled = option { ffi::MDB_CP_COMPACT } else { 0 };
+        let env = self.clone();
+        let copier = thread::spawn(move || {
+            let fd = get_file_fd(&pipe_writer);
+            // Like in `copy_to_fd`, the map must not be resized while it is copied.
+            env.txn_started();
+            let result = unsafe { mdb_result(ffi::mdb_env_copy2fd(env.env_mut_ptr(), fd, flags)) };
+            env.txn_ended();
+            result
+        });
+
+        let copied = io::copy(&mut reader, &mut writer);
+        // The copier thread stops with a broken pipe if the writer failed.
+        drop(reader);
+        let result = copier.join().expect("the copier thread panicked");
+
+        let copied = copied?;
+        result?;
+        writer.flush()?;
+
+        Ok(copied)
+    }
+
+    /// Restores a data file, like the ones written by [`Env::copy_to_path`], into
+    /// the given environment directory, returns the number of bytes written.
+    ///
+    /// The meta pages, the page size and the version of the data file are validated
+    /// before it atomically replaces the `data.mdb` file of the directory, which is
+    /// created if needed. An environment that is opened by this process cannot be
+    /// replaced, the environment must not be opened by other processes either.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{CompactionOption, Database, Env, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let path = Path::new("target").join("restore-source.mdb");
+    /// fs::create_dir_all(&path)?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(&path)?;
+    /// let db: Database<Str, Str> = env.create_database(None)?;
+    /// env.write(|wtxn| db.put(wtxn, &"hello", &"world"))?;
+    ///
+    /// let backup = Path::new("target").join("restore-backup.mdb");
+    /// env.copy_to_path(&backup, CompactionOption::Enabled)?;
+    ///
+    /// // An opened environment cannot be replaced.
+    /// assert!(Env::restore_from_path(&backup, &path).is_err());
+    /// // Only valid data files are restored.
+    /// assert!(Env::restore_from(&b"not a data file"[..], "target/restored.mdb").is_err());
+    ///
+    /// Env::restore_from_path(&backup, "target/restored.mdb")?;
+    /// let restored = EnvOpenOptions::new().open("target/restored.mdb")?;
+    /// let db: Database<Str, Str> = restored.open_database(None)?.unwrap();
+    /// let rtxn = restored.read_txn()?;
+    /// assert_eq!(db.get(&rtxn, &"hello")?, Some("world"));
+    /// # Ok(()) }
+    /// ```
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn restore_from<R: io::Read, P: AsRef<Path>>(reader: R, target_dir: P) -> Result<u64> {
+        fs::create_dir_all(&target_dir)?;
+        let target_dir = canonicalize_path(target_dir.as_ref())?;
+
+        let lock_unopened = || {
+            let lock = OPENED_ENV.read().unwrap();
+            if lock.contains_key(&target_dir) {
+                let msg = format!("the environment at {} is currently opened", target_dir.display());
+                Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+            } else {
+                Ok(lock)
+            }
+        };
+
+        // The data file is copied without the lock, which is only kept to make
+        // sure nobody opens the environment while its data file is replaced.
+        drop(lock_unopened()?);
+        Ok(restore_data_file(reader, &target_dir, lock_unopened)?)
+    }
+
+    /// Restores the data file at the given path into the given environment directory,
+    /// see [`Env::restore_from`].
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn restore_from_path<P: AsRef<Path>, Q: AsRef<Path>>(path: P, target_dir: Q) -> Result<u64> {
+        let file = File::open(path)?;
+        Env::restore_from(io::BufReader::new(file), target_dir)
+    }
+
+    /// Writes the given databases into the writer in the `mdb_dump` format, `None` being the
+    /// unnamed database. The entries are dumped as raw bytes, independently of their codecs.
+    ///
+    /// The named databases stored in the unnamed database are not dumped with it,
+    /// they must be listed explicitly.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{DumpFormat, EnvOpenOptions, Database};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("dump-source.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("dump-source.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(Some("words"))?;
+    /// let dups = env.database_options().types::<Str, Str>().name("dups").dup_sort(true).create()?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &"hello", &"world\n")?;
+    /// dups.clear(&mut wtxn)?;
+    /// dups.put(&mut wtxn, &"colors", &"blue")?;
+    /// dups.put(&mut wtxn, &"colors", &"red")?;
+    /// wtxn.commit()?;
+    ///
+    /// let mut dump = Vec::new();
+    /// env.dump(&mut dump, &[Some("words"), Some("dups")], DumpFormat::Print)?;
+    /// assert!(String::from_utf8(dump.clone())?.contains(" hello\n world\\0a\n"));
+    ///
+    /// fs::create_dir_all(Path::new("target").join("dump-target.mdb"))?;
+    /// let target = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("dump-target.mdb"))?;
+    /// target.load(&dump[..])?;
+    ///
+    /// let dups = target.database_options().types::<Str, Str>().name("dups").dup_sort(true).open()?.unwrap();
+    /// let rtxn = target.read_txn()?;
+    /// let colors: Vec<_> = dups.get_duplicates(&rtxn, &"colors")?.unwrap().collect::<Result<_, _>>()?;
+    /// assert_eq!(colors, vec![("colors", "blue"), ("colors", "red")]);
+    /// # Ok(()) }
+    /// ```
+    pub fn dump<W: io::Write>(
+        &self,
+        writer: W,
+        databases: &[Option<&str>],
+        format: DumpFormat,
+    ) -> Result<()> {
+        dump::dump(self, writer, databases, format)
+    }
+
+    /// Loads the databases of a dump in the `mdb_dump` format, see [`Env::dump`].
+    ///
+    /// The databases are created if they don't exist, with the flags of the dump,
+    /// and all the entries are written in a single write transaction.
+    pub fn load<R: io::BufRead>(&self, reader: R) -> Result<()> {
+        dump::load(self, reader)
+    }
+
+    pub fn force_sync(&self) -> Result<()> {
+        sync_env(self.0.env)
+    }
+
+    /// Returns the canonicalized path where this env lives.
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    /// Returns the effective flags this environment has been opened with.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{EnvFlag, EnvOpenOptions};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("env-flags.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .no_meta_sync(true)
+    ///     .no_read_ahead(true)
+    ///     .open(Path::new("target").join("env-flags.mdb"))?;
+    ///
+    /// let flags = env.flags()?;
+    /// assert!(flags.contains(EnvFlag::NoMetaSync));
+    /// assert!(flags.contains(EnvFlag::NoReadAhead));
+    /// assert!(!flags.contains(EnvFlag::WriteMap));
+    /// # Ok(()) }
+    /// ```
+    pub fn flags(&self) -> Result<EnvFlags> {
+        self.raw_flags().map(EnvFlags)
+    }
+
+    /// Lists the reader slots that are in use in the lock file of this environment,
+    /// by any process, a reader that uses an old snapshot prevents the reuse of the
+    /// pages that have been freed since.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::EnvOpenOptions;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("readers.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(Path::new("target").join("readers.mdb"))?;
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// let readers = env.readers()?;
+    /// assert!(readers.iter().any(|r| r.pid == std::process::id() && r.txn_id.is_some()));
+    /// drop(rtxn);
+    ///
+    /// // The readers of the processes that crashed are cleared.
+    /// assert_eq!(env.clear_stale_readers()?, 0);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn readers(&self) -> Result<Vec<ReaderInfo>> {
+        unsafe extern "C" fn push_reader(msg: *const libc::c_char, ctx: *mut libc::c_void) -> libc::c_int {
+            let readers = &mut *(ctx as *mut Vec<ReaderInfo>);
+            let line = CStr::from_ptr(msg).to_string_lossy();
+            readers.extend(ReaderInfo::from_line(&line));
+            0
+        }
+
+        let mut readers = Vec::new();
+        let ctx = &mut readers as *mut Vec<ReaderInfo> as *mut libc::c_void;
+        let result = unsafe { ffi::mdb_reader_list(self.0.env, Some(push_reader), ctx) };
+        if result < 0 {
+            return Err(Error::Io(io::Error::new(io::ErrorKind::Other, "could not list the readers")));
+        }
+
+        Ok(readers)
+    }
+
+    /// Lists the reader slots that are in use in the lock file of this environment,
+    /// by any process, a reader that uses an old snapshot prevents the reuse of the
+    /// pages that have been freed since.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::EnvOpenOptions;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("readers.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(Path::new("target").join("readers.mdb"))?;
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// let readers = env.readers()?;
+    /// assert!(readers.iter().any(|r| r.pid == std::process::id() && r.txn_id.is_some()));
+    /// drop(rtxn);
+    ///
+    /// // The readers of the processes that crashed are cleared.
+    /// assert_eq!(env.clear_stale_readers()?, 0);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+    pub fn readers(&self) -> Result<Vec<ReaderInfo>> {
+        #[allow(clippy::too_many_arguments)]
+        unsafe extern "C" fn push_reader(
+            ctx: *mut libc::c_void,
+            _num: libc::c_int,
+            _slot: libc::c_int,
+            pid: ffi::mdbx_pid_t,
+            thread: ffi::mdbx_tid_t,
+            txnid: u64,
+            _lag: u64,
+            _bytes_used: usize,
+            _bytes_retained: usize,
+        ) -> libc::c_int {
+            let readers = &mut *(ctx as *mut Vec<ReaderInfo>);
+            // MDBX reports a zero transaction id for the readers that are not active.
+            let txn_id = if txnid == 0 { None } else { Some(txnid) };
+            readers.push(ReaderInfo { pid: pid as u32, thread: thread as u64, txn_id });
+            0
+        }
+
+        let mut readers = Vec::new();
+        let ctx = &mut readers as *mut Vec<ReaderInfo> as *mut libc::c_void;
+        let result = unsafe { ffi::mdb_reader_list(self.0.env, Some(push_reader), ctx) };
+        if result < 0 {
+            return Err(Error::Io(io::Error::new(io::ErrorKind::Other, "could not list the readers")));
+        }
+
+        Ok(readers)
+    }
+
+    /// Clears the reader slots of the processes that no longer exist,
+    /// returns the number of slots that have been cleared.
+    pub fn clear_stale_readers(&self) -> Result<usize> {
+        let mut dead: libc::c_int = 0;
+        unsafe { mdb_result(ffi::mdb_reader_check(self.0.env, &mut dead))? };
+        Ok(dead as usize)
+    }
+
+    /// Changes the size of the memory map of this environment, it must be
+    /// a multiple of the system page size.
+    ///
+    /// No transaction must be alive in this process while the map is resized, an error
+    /// is returned if there is any, the copies of the environment count as read transactions.
+    /// New transactions wait for the resize to be done.
+    ///
+    /// When another process grew the map, beginning a transaction returns
+    /// a [`MdbError::MapResized`] error, resizing to `0` adopts the new size.
+    ///
+    /// [`MdbError::MapResized`]: crate::MdbError::MapResized
+    pub fn resize(&self, new_size: usize) -> Result<()> {
+        if new_size % page_size::get() != 0 {
+            let msg = format!(
+                "map size ({}) must be a multiple of the system page size ({})",
+                new_size, page_size::get()
+            );
+            return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+        }
+
+        let _resize = self.0.resize_lock.write().unwrap();
+        let active_txns = self.0.active_txns.load(atomic::Ordering::SeqCst);
+        if active_txns != 0 {
+            let msg = format!("cannot resize the map while {} transactions are alive", active_txns);
+            return Err(Error::Io(io::Error::new(io::ErrorKind::Other, msg)));
+        }
+
+        unsafe { mdb_result(ffi::mdb_env_set_mapsize(self.0.env, new_size))? };
+
+        Ok(())
+    }
+
+    /// Grows the memory map following the [`MapGrowth`] policy defined with
+    /// [`EnvOpenOptions::map_growth`], returns the new map size or `None` if there is
+    /// no policy or the maximum map size is already reached.
+    ///
+    /// Like [`Env::resize`], no transaction must be alive in this process. A failed
+    /// write transaction can be retried once the map grew.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions, MapGrowth, MdbError};
+    /// use heed::types::*;
+    /// use heed::byteorder::BigEndian;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("map-growth.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(1024 * 1024) // 1MB
+    ///     .map_growth(MapGrowth::Factor(2.0), 64 * 1024 * 1024) // up to 64MB
+    ///     .open(Path::new("target").join("map-growth.mdb"))?;
+    ///
+    /// type BEU32 = U32<BigEndian>;
+    /// let db: Database<OwnedType<BEU32>, ByteSlice> = env.create_database(None)?;
+    ///
+    /// let value = vec![42; 4096];
+    /// let write_values = || -> heed::Result<()> {
+    ///     let mut wtxn = env.write_txn()?;
+    ///     db.clear(&mut wtxn)?;
+    ///     for i in 0..1000 {
+    ///         db.put(&mut wtxn, &BEU32::new(i), &&value[..])?;
+    ///     }
+    ///     wtxn.commit()
+    /// };
+    ///
+    /// loop {
+    ///     match write_values() {
+    ///         Err(heed::Error::Mdb(MdbError::MapFull)) => {
+    ///             env.grow_map()?.expect("the maximum map size has been reached");
+    ///         }
+    ///         result => break result?,
+    ///     }
+    /// }
+    ///
+    /// assert!(env.info()?.map_size > 4 * 1024 * 1024);
+    /// # Ok(()) }
+    /// ```
+    pub fn grow_map(&self) -> Result<Option<usize>> {
+        let (growth, max_size) = match self.0.map_growth {
+            Some(policy) => policy,
+            None => return Ok(None),
+        };
+
+        let current_size = self.info()?.map_size;
+        if current_size >= max_size {
+            return Ok(None);
+        }
+
+        let new_size = match growth {
+            MapGrowth::Factor(factor) => (current_size as f64 * factor) as usize,
+            MapGrowth::Step(step) => current_size.saturating_add(step),
+        };
+        let page_size = page_size::get();
+        let new_size = new_size.saturating_add(page_size - 1) / page_size * page_size;
+        let new_size = new_size.min(max_size);
+
+        self.resize(new_size).map(|()| Some(new_size))
+    }
+
+    /// Returns the statistics of the main database of this environment,
+    /// the one that stores the names of the other databases.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::EnvOpenOptions;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("env-stat.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("env-stat.mdb"))?;
+    ///
+    /// let stat = env.stat()?;
+    /// assert!(stat.page_size.is_power_of_two());
+    ///
+    /// let info = env.info()?;
+    /// assert_eq!(info.map_size, 10 * 1024 * 1024);
+    /// let used_bytes = (info.last_page_number + 1) * stat.page_size as usize;
+    /// assert!(used_bytes <= info.map_size);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn stat(&self) -> Result<DatabaseStat> {
+        let mut stat = mem::MaybeUninit::<ffi::MDB_Stat>::uninit();
+        unsafe { mdb_result(ffi::mdb_env_stat(self.0.env, stat.as_mut_ptr()))? };
+        Ok(DatabaseStat::from_raw(unsafe { stat.assume_init() }))
+    }
+
+    /// Returns the statistics of the main database of this environment,
+    /// the one that stores the names of the other databases.
+    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+    pub fn stat(&self) -> Result<DatabaseStat> {
+        let mut stat = mem::MaybeUninit::<ffi::MDB_Stat>::uninit();
+        let size = mem::size_of::<ffi::MDB_Stat>();
+        unsafe { mdb_result(ffi::mdb_env_stat(self.0.env, stat.as_mut_ptr(), size))? };
+        Ok(DatabaseStat::from_raw(unsafe { stat.assume_init() }))
+    }
+
+    /// Returns information about this environment, like the size of the memory
+    /// map, the last used page, the last committed transaction and the readers.
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn info(&self) -> Result<EnvInfo> {
+        let mut info = mem::MaybeUninit::<ffi::MDB_envinfo>::uninit();
+        unsafe { mdb_result(ffi::mdb_env_info(self.0.env, info.as_mut_ptr()))? };
+        Ok(EnvInfo::from_raw(unsafe { info.assume_init() }))
+    }
+
+    /// Returns information about this environment, like the size of the memory
+    /// map, the last used page, the last committed transaction and the readers.
+    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+    pub fn info(&self) -> Result<EnvInfo> {
+        let mut info = mem::MaybeUninit::<ffi::MDB_envinfo>::uninit();
+        let size = mem::size_of::<ffi::MDB_envinfo>();
+        unsafe { mdb_result(ffi::mdb_env_info(self.0.env, info.as_mut_ptr(), size))? };
+        Ok(EnvInfo::from_raw(unsafe { info.assume_init() }))
+    }
+
+    /// Returns an `EnvClosingEvent` that can be used to wait for the closing event,
+    /// multiple threads can wait on this event.
+    ///
+    /// Make sure that you drop all the copies of `Env`s you have, env closing are triggered
+    /// when all references are dropped, the last one will eventually close the environment.
+    pub fn prepare_for_closing(self) -> EnvClosingEvent {
+        self.0.stop_flusher();
+
+        let mut lock = OPENED_ENV.write().unwrap();
+        let env = lock.get_mut(&self.0.path);
+
+        match env {
+            None => panic!("cannot find the env that we are trying to close"),
+            Some((env, signal_event)) => {
+                // We remove the env from the global list and replace it with a None.
+                let _env = env.take();
+                let signal_event = signal_event.clone();
+
+                // we must make sure we release the lock before we drop the env
+                // as the drop of the EnvInner also tries to lock the OPENED_ENV
+                // global and we don't want to trigger a dead-lock.
+                drop(lock);
+
+                EnvClosingEvent(signal_event)
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+fn is_map_resized(error: &Error) -> bool {
+    matches!(error, Error::Mdb(MdbError::MapResized))
+}
+
+#[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+fn is_map_resized(_error: &Error) -> bool {
+    false
+}
+
+/// Opens a database handle without registering it, returns `None` if the database doesn't exist.
+///
+/// The handle is closed when the transaction is aborted if it wasn't already opened.
+pub(crate) fn open_dbi<T>(txn: &RoTxn<T>, name: Option<&str>, flags: u32) -> Result<Option<u32>> {
+    let mut dbi = 0;
+    let name = match name.map(CString::new).transpose() {
+        Ok(name) => name,
+        Err(e) => return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e))),
+    };
+    let name_ptr = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+
+    let _lock = txn.env.0.dbi_open_mutex.lock().unwrap();
+    match unsafe { mdb_result(ffi::mdb_dbi_open(txn.txn, name_ptr, flags, &mut dbi)) } {
+        Ok(()) => Ok(Some(dbi)),
+        Err(e) if e.not_found() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Retrieves the flags the given database has been created with.
+pub(crate) fn dbi_flags<T>(txn: &RoTxn<T>, dbi: u32) -> Result<u32> {
+    let mut flags = 0;
+    unsafe { mdb_result(ffi::mdb_dbi_flags(txn.txn, dbi, &mut flags))? };
+    Ok(flags)
+}
+
+/// Makes sure that the types, flags and comparators of an opened database are the same
+/// as the ones this database was previously opened with, if it was.
+///
+/// Not specifying a comparator keeps the one the database was previously opened with.
+fn check_dbi(
+    dbis: &HashMap<u32, DbiEntry>,
+    dbi: u32,
+    types: (TypeId, TypeId),
+    db_flags: u32,
+    expected_flags: Option<u32>,
+    comparators: Comparators,
+) -> Result<()> {
+    let entry = match dbis.get(&dbi) {
+        Some(entry) => entry,
+        None if matches!(expected_flags, Some(flags) if flags != db_flags) => {
+            return Err(Error::InvalidDatabaseFlags)
+        }
+        None => return Ok(()),
+    };
+
+    let same_comparator = |old: Option<TypeId>, new: Option<TypeId>| new.is_none() || old == new;
+    let (old_key, old_dup) = entry.comparators.type_ids();
+    let (new_key, new_dup) = comparators.type_ids();
+
+    if entry.types != types
+        || !same_comparator(old_key, new_key)
+        || !same_comparator(old_dup, new_dup)
+    {
+        Err(Error::InvalidDatabaseTyping)
+    } else if matches!(expected_flags, Some(flags) if flags != entry.flags) {
+        Err(Error::InvalidDatabaseFlags)
+    } else {
+        Ok(())
+    }
+}
+
+/// Registers the types, flags and comparators of an opened database,
+/// once they have been checked with [`check_dbi`].
+fn register_dbi(
+    dbis: &mut HashMap<u32, DbiEntry>,
+    dbi: u32,
+    name: Option<&str>,
+    types: (TypeId, TypeId),
+    db_flags: u32,
+    comparators: Comparators,
+) {
+    dbis.entry(dbi).or_insert_with(|| DbiEntry {
+        name: name.map(str::to_owned),
+        types,
+        flags: db_flags,
+        comparators,
+    });
+}
+
+/// Sets the custom comparison functions of a database, they must be set
+/// before any data access and each time the environment is opened.
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+fn set_comparators<T>(txn: &RoTxn<T>, dbi: u32, comparators: Comparators) -> Result<()> {
+    if let Some((_, cmp)) = comparators.key {
+        unsafe { mdb_result(ffi::mdb_set_compare(txn.txn, dbi, cmp))? };
+    }
+    if let Some((_, cmp)) = comparators.dup {
+        unsafe { mdb_result(ffi::mdb_set_dupsort(txn.txn, dbi, cmp))? };
+    }
+    Ok(())
+}
+
+/// MDBX only accepts the comparison functions when opening a database,
+/// the [`DatabaseOpenOptions`] reject them before.
+#[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+fn set_comparators<T>(_txn: &RoTxn<T>, _dbi: u32, _comparators: Comparators) -> Result<()> {
+    Ok(())
+}
+
+/// Returns an error if the database has been opened with a custom key comparator or
+/// created with the `MDB_REVERSEKEY` or `MDB_INTEGERKEY` flags, the keys sharing a prefix
+/// are then not necessarily sorted next to each other.
+pub(crate) fn check_prefix_ordering<T>(txn: &RoTxn<T>, dbi: u32) -> Result<()> {
+    if dbi_flags(txn, dbi)? & (ffi::MDB_REVERSEKEY | ffi::MDB_INTEGERKEY) != 0 {
+        let msg = "prefix iteration is not supported with reversed or integer keys";
+        return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+    }
+
+    let dbis = txn.env.0.dbi_open_mutex.lock().unwrap();
+    match dbis.get(&dbi).and_then(|entry| entry.comparators.key) {
+        Some((id, _)) if id != TypeId::of::<DefaultComparator>() => {
+            let msg = "prefix iteration is not supported with a custom key comparator";
+            Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The C comparison function that LMDB calls, it forwards to the given [`Comparator`].
+unsafe extern "C" fn custom_cmp_wrapper<C: Comparator>(
+    a: *const ffi::MDB_val,
+    b: *const ffi::MDB_val,
+) -> libc::c_int {
+    let a = crate::from_val(*a);
+    let b = crate::from_val(*b);
+    // Unwinding through the C frames of LMDB is undefined behavior.
+    match panic::catch_unwind(|| C::compare(a, b)) {
+        Ok(Ordering::Less) => -1,
+        Ok(Ordering::Equal) => 0,
+        Ok(Ordering::Greater) => 1,
+        Err(_) => process::abort(),
+    }
+}
+
+/// The default key codec of a [`DatabaseOpenOptions`], returns raw bytes.
+pub type UntypedKey = ByteSlice<'static>;
+
+/// The default data codec of a [`DatabaseOpenOptions`], returns raw bytes.
+pub type UntypedData = ByteSlice<'static>;
+
+/// Options and flags which can be used to configure how a [`Database`] is opened or created.
+///
+/// The database flags can only be set when the database is created, opening an existing
+/// database with different flags returns an [`Error::InvalidDatabaseFlags`] error.
+///
+/// ```
+/// use std::fs;
+/// use std::path::Path;
+/// use heed::{EnvOpenOptions, Database};
+/// use heed::types::*;
+/// use heed::byteorder::BigEndian;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// fs::create_dir_all(Path::new("target").join("database-options.mdb"))?;
+/// let env = EnvOpenOptions::new()
+///     .max_dbs(10)
+///     .open(Path::new("target").join("database-options.mdb"))?;
+///
+/// type BEU32 = U32<BigEndian>;
+///
+/// // A database where keys are sorted from the highest to the lowest
+/// // and each key can have multiple fixed-size values.
+/// let db: Database<Str, OwnedType<BEU32>> = env
+///     .database_options()
+///     .types::<Str, OwnedType<BEU32>>()
+///     .name("scores")
+///     .reverse_key(true)
+///     .dup_sort(true)
+///     .dup_fixed(true)
+///     .create()?;
+///
+/// let mut wtxn = env.write_txn()?;
+/// # db.clear(&mut wtxn)?;
+/// db.put(&mut wtxn, &"abc", &BEU32::new(3))?;
+/// db.put(&mut wtxn, &"abc", &BEU32::new(1))?;
+/// db.put(&mut wtxn, &"cba", &BEU32::new(2))?;
+///
+/// let rets: Vec<_> = db.iter(&wtxn)?.collect::<Result<_, _>>()?;
+/// assert_eq!(rets, vec![
+///     ("cba", BEU32::new(2)),
+///     ("abc", BEU32::new(1)),
+///     ("abc", BEU32::new(3)),
+/// ]);
+///
+/// wtxn.commit()?;
+///
+/// // Opening it with different flags is an error.
+/// let ret = env.database_options().types::<Str, OwnedType<BEU32>>().name("scores").open();
+/// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseFlags)));
+///
+/// // Some flags cannot be used without others.
+/// let ret = env.database_options().name("invalid").dup_fixed(true).create();
+/// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseFlags)));
+/// # Ok(()) }
+/// ```
+pub struct DatabaseOpenOptions<'e, KC, DC> {
+    env: &'e Env,
+    name: Option<String>,
+    flags: u32,
+    comparators: Comparators,
+    schema_ids: (u32, u32),
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'e, KC, DC> DatabaseOpenOptions<'e, KC, DC> {
+    /// Change the codec types of the database, specifying the codecs.
+    pub fn types<KC2, DC2>(&self) -> DatabaseOpenOptions<'e, KC2, DC2> {
+        DatabaseOpenOptions {
+            env: self.env,
+            name: self.name.clone(),
+            flags: self.flags,
+            comparators: self.comparators,
+            schema_ids: (0, 0),
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of the database, specifying the new codec.
+    pub fn key_type<KC2>(&self) -> DatabaseOpenOptions<'e, KC2, DC> {
+        self.types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of the database, specifying the new codec.
+    pub fn data_type<DC2>(&self) -> DatabaseOpenOptions<'e, KC, DC2> {
+        self.types::<KC, DC2>()
+    }
+
+    /// The name of the database to open, the unnamed database is used by default.
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// Keys are strings to be compared in reverse order, from the end of the strings
+    /// to the beginning (`MDB_REVERSEKEY`).
+    ///
+    /// The prefix iterators of the database return an error, as the keys
+    /// sharing a prefix are not sorted together.
+    pub fn reverse_key(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_REVERSEKEY, enabled)
+    }
+
+    /// Keys are binary integers in native byte order, either `u32` or `usize`,
+    /// and will be sorted as such (`MDB_INTEGERKEY`).
+    ///
+    /// The keys must all be of the same size. The prefix iterators of the database
+    /// return an error, as the keys sharing a prefix are not sorted together.
+    pub fn integer_key(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_INTEGERKEY, enabled)
+    }
 
-        let result = unsafe {
-            mdb_result(ffi::mdb_dbi_open(
-                wtxn.txn.txn,
-                name_ptr,
-                ffi::MDB_CREATE,
-                &mut dbi,
-            ))
-        };
+    /// Duplicate keys may be used in the database, or, from another perspective,
+    /// keys may have multiple data items, stored in sorted order (`MDB_DUPSORT`).
+    pub fn dup_sort(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_DUPSORT, enabled)
+    }
 
-        drop(name);
+    /// The data items of a key are all the same size, this option is only allowed
+    /// in combination with [`dup_sort`] (`MDB_DUPFIXED`).
+    ///
+    /// [`dup_sort`]: DatabaseOpenOptions::dup_sort
+    pub fn dup_fixed(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_DUPFIXED, enabled)
+    }
 
-        match result {
-            Ok(()) => {
-                wtxn.commit()?;
+    /// The duplicate data items are binary integers similar to [`integer_key`] keys,
+    /// this option is only allowed in combination with [`dup_sort`] (`MDB_INTEGERDUP`).
+    ///
+    /// [`integer_key`]: DatabaseOpenOptions::integer_key
+    /// [`dup_sort`]: DatabaseOpenOptions::dup_sort
+    pub fn integer_dup(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_INTEGERDUP, enabled)
+    }
 
-                let old_types = lock.entry(dbi).or_insert(types);
+    /// The duplicate data items are compared as strings in reverse order, this option
+    /// is only allowed in combination with [`dup_sort`] (`MDB_REVERSEDUP`).
+    ///
+    /// [`dup_sort`]: DatabaseOpenOptions::dup_sort
+    pub fn reverse_dup(&mut self, enabled: bool) -> &mut Self {
+        self.set_flag(ffi::MDB_REVERSEDUP, enabled)
+    }
 
-                if *old_types == types {
-                    Ok(dbi)
-                } else {
-                    Err(Error::InvalidDatabaseTyping)
-                }
-            }
-            Err(e) => Err(e.into()),
-        }
+    /// Sorts the keys of the database with the given [`Comparator`] instead of
+    /// the default lexicographic order of their bytes.
+    ///
+    /// LMDB doesn't store the comparison function, the database must always be opened
+    /// with the same comparator, opening it with a different one returns an
+    /// [`Error::InvalidDatabaseTyping`] error. The prefix iterators of the database
+    /// return an error, as the keys sharing a prefix are not necessarily sorted together.
+    ///
+    /// Custom comparators are only supported with LMDB, MDBX returns an error.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::convert::TryInto;
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Comparator, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// /// Compares native-endian u32s, the fastest representation to encode.
+    /// enum NativeU32Cmp {}
+    ///
+    /// impl Comparator for NativeU32Cmp {
+    ///     fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    ///         // A comparator must not panic, the bytes that aren't
+    ///         // u32s are sorted in lexicographic order instead.
+    ///         match (a.try_into(), b.try_into()) {
+    ///             (Ok(a), Ok(b)) => u32::from_ne_bytes(a).cmp(&u32::from_ne_bytes(b)),
+    ///             _ => a.cmp(b),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("comparator.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .max_dbs(10)
+    ///     .open(Path::new("target").join("comparator.mdb"))?;
+    ///
+    /// let db = env.database_options()
+    ///     .types::<OwnedType<u32>, Str>()
+    ///     .name("native-ints")
+    ///     .key_comparator::<NativeU32Cmp>()
+    ///     .create()?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// # db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &256, &"two-hundred-fifty-six")?;
+    /// db.put(&mut wtxn, &1, &"one")?;
+    /// db.put(&mut wtxn, &42, &"forty-two")?;
+    ///
+    /// let keys: Vec<_> = db.iter(&wtxn)?.map(|r| r.map(|(k, _)| k)).collect::<Result<_, _>>()?;
+    /// assert_eq!(keys, vec![1, 42, 256]);
+    ///
+    /// let range: Vec<_> = db.range(&wtxn, 2..=256)?.collect::<Result<_, _>>()?;
+    /// assert_eq!(range, vec![(42, "forty-two"), (256, "two-hundred-fifty-six")]);
+    ///
+    /// wtxn.commit()?;
+    ///
+    /// // Opening it with another comparator is an error.
+    /// let ret = env.database_options()
+    ///     .types::<OwnedType<u32>, Str>()
+    ///     .name("native-ints")
+    ///     .key_comparator::<heed::DefaultComparator>()
+    ///     .open();
+    /// assert!(matches!(ret, Err(heed::Error::InvalidDatabaseTyping)));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Error::InvalidDatabaseTyping`]: crate::Error::InvalidDatabaseTyping
+    pub fn key_comparator<C: Comparator + 'static>(&mut self) -> &mut Self {
+        self.comparators.key = Some((TypeId::of::<C>(), Some(custom_cmp_wrapper::<C>)));
+        self
     }
 
-    pub fn write_txn(&self) -> Result<RwTxn> {
-        RwTxn::new(self)
+    /// Sorts the duplicate data items of the keys with the given [`Comparator`] instead
+    /// of the default lexicographic order of their bytes, this option is only allowed
+    /// in combination with [`dup_sort`].
+    ///
+    /// Custom comparators are only supported with LMDB, MDBX returns an error.
+    ///
+    /// [`dup_sort`]: DatabaseOpenOptions::dup_sort
+    pub fn dup_comparator<C: Comparator + 'static>(&mut self) -> &mut Self {
+        self.comparators.dup = Some((TypeId::of::<C>(), Some(custom_cmp_wrapper::<C>)));
+        self
     }
 
-    pub fn typed_write_txn<T>(&self) -> Result<RwTxn<T>> {
-        RwTxn::<T>::new(self)
+    /// Stores the [`CodecSchema::SCHEMA_ID`]s of the codecs in the fingerprint of the database,
+    /// see [`EnvOpenOptions::codec_fingerprints`]. Changing the codecs resets the ids.
+    ///
+    /// The database must then always be opened with these ids, not with [`Env::open_database`].
+    pub fn codec_schemas(&mut self) -> &mut Self
+    where
+        KC: CodecSchema,
+        DC: CodecSchema,
+    {
+        self.schema_ids = (KC::SCHEMA_ID, DC::SCHEMA_ID);
+        self
     }
 
-    pub fn nested_write_txn<'e, 'p: 'e, T>(&'e self, parent: &'p mut RwTxn<T>) -> Result<RwTxn<'e, 'p, T>> {
-        RwTxn::nested(self, parent)
+    fn set_flag(&mut self, flag: u32, enabled: bool) -> &mut Self {
+        if enabled {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+        self
     }
 
-    pub fn read_txn(&self) -> Result<RoTxn> {
-        RoTxn::new(self)
+    /// Makes sure that the flags can be used together, returns an
+    /// [`Error::InvalidDatabaseFlags`] error otherwise.
+    fn validated_flags(&self) -> Result<u32> {
+        // MDBX only accepts the comparison functions when opening a database.
+        if cfg!(all(feature = "mdbx", not(feature = "lmdb")))
+            && (self.comparators.key.is_some() || self.comparators.dup.is_some())
+        {
+            let msg = "custom comparators are not supported with MDBX";
+            return Err(Error::Io(io::Error::new(io::ErrorKind::Unsupported, msg)));
+        }
+
+        let dup_flags = ffi::MDB_DUPFIXED | ffi::MDB_INTEGERDUP | ffi::MDB_REVERSEDUP;
+        let dup_sort = self.flags & ffi::MDB_DUPSORT != 0;
+        let has_flags = |flags| self.flags & flags == flags;
+
+        // The dup_fixed, integer_dup and reverse_dup flags and a duplicate data
+        // comparator require the dup_sort flag, the integer and reverse orders
+        // cannot be used together.
+        if (!dup_sort && (self.flags & dup_flags != 0 || self.comparators.dup.is_some()))
+            || has_flags(ffi::MDB_INTEGERKEY | ffi::MDB_REVERSEKEY)
+            || has_flags(ffi::MDB_INTEGERDUP | ffi::MDB_REVERSEDUP)
+        {
+            Err(Error::InvalidDatabaseFlags)
+        } else {
+            Ok(self.flags)
+        }
     }
+}
 
-    pub fn typed_read_txn<T>(&self) -> Result<RoTxn<T>> {
-        RoTxn::new(self)
+impl<KC, DC> DatabaseOpenOptions<'_, KC, DC>
+where
+    KC: 'static,
+    DC: 'static,
+{
+    /// Opens an already existing database, returns `None` if it doesn't exist.
+    ///
+    /// The database must have been created with the same flags.
+    pub fn open(&self) -> Result<Option<Database<KC, DC>>> {
+        let flags = self.validated_flags()?;
+        let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
+        let fingerprint = self.env.fingerprint::<KC, DC>(self.schema_ids);
+        Ok(self
+            .env
+            .raw_open_database(self.name.as_deref(), types, Some(flags), self.comparators, fingerprint)?
+            .map(|db| Database::new(self.env.env_mut_ptr() as _, db)))
     }
 
-    // TODO rename into `copy_to_file` for more clarity
-    pub fn copy_to_path<P: AsRef<Path>>(&self, path: P, option: CompactionOption) -> Result<File> {
-        let file = File::create(&path)?;
-        let fd = get_file_fd(&file);
+    /// Creates the database if it doesn't already exist and opens it.
+    pub fn create(&self) -> Result<Database<KC, DC>> {
+        let mut parent_wtxn = self.env.write_txn()?;
+        let db = self.create_with_txn(&mut parent_wtxn)?;
+        parent_wtxn.commit()?;
+        Ok(db)
+    }
 
-        unsafe { self.copy_to_fd(fd, option)?; }
+    /// Creates the database if it doesn't already exist and opens it,
+    /// by using the given parent write transaction.
+    pub fn create_with_txn(&self, parent_wtxn: &mut RwTxn) -> Result<Database<KC, DC>> {
+        let flags = self.validated_flags()?;
+        let types = (TypeId::of::<KC>(), TypeId::of::<DC>());
+        let name = self.name.as_deref();
+        let fingerprint = self.env.fingerprint::<KC, DC>(self.schema_ids);
+        self.env
+            .raw_create_database(name, types, Some(flags), self.comparators, fingerprint, parent_wtxn)
+            .map(|db| Database::new(self.env.env_mut_ptr() as _, db))
+    }
+}
 
-        // We reopen the file to make sure the cursor is at the start,
-        // even a seek to start doesn't work properly.
-        let file = File::open(path)?;
+/// An environment opened in read-only mode with [`EnvOpenOptions::open_read_only`],
+/// only the operations that don't write to the environment are available.
+#[derive(Clone)]
+pub struct ReadOnlyEnv(Env);
 
-        Ok(file)
+impl ReadOnlyEnv {
+    /// Opens an already existing database, see [`Env::open_database`].
+    pub fn open_database<KC, DC>(&self, name: Option<&str>) -> Result<Option<Database<KC, DC>>>
+    where
+        KC: 'static,
+        DC: 'static,
+    {
+        self.0.open_database(name)
     }
 
-    pub unsafe fn copy_to_fd(&self, fd: ffi::mdb_filehandle_t, option: CompactionOption) -> Result<()> {
-        let flags = if let CompactionOption::Enabled = option { ffi::MDB_CP_COMPACT } else { 0 };
+    /// Returns the names of the named databases, see [`Env::database_names`].
+    pub fn database_names<T>(&self, rtxn: &RoTxn<T>) -> Result<Vec<String>> {
+        self.0.database_names(rtxn)
+    }
 
-        mdb_result(ffi::mdb_env_copy2fd(self.0.env, fd, flags))?;
+    /// Begins a new read transaction, see [`Env::read_txn`].
+    pub fn read_txn(&self) -> Result<RoTxn<'_>> {
+        self.0.read_txn()
+    }
 
-        Ok(())
+    /// Begins a new typed read transaction, see [`Env::typed_read_txn`].
+    pub fn typed_read_txn<T>(&self) -> Result<RoTxn<'_, T>> {
+        self.0.typed_read_txn()
     }
 
-    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
-    pub fn force_sync(&self) -> Result<()> {
-        unsafe { mdb_result(ffi::mdb_env_sync(self.0.env, 1))? }
+    /// Runs the closure in a new read transaction, see [`Env::read`].
+    pub fn read<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&RoTxn) -> Result<T>,
+    {
+        self.0.read(f)
+    }
 
-        Ok(())
+    /// Copies the environment to the given path, see [`Env::copy_to_path`].
+    pub fn copy_to_path<P: AsRef<Path>>(&self, path: P, option: CompactionOption) -> Result<File> {
+        self.0.copy_to_path(path, option)
     }
 
-    #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
-    pub fn force_sync(&self) -> Result<()> {
-        unsafe { mdb_result(ffi::mdb_env_sync(self.0.env))? }
+    /// Copies the environment to the given file descriptor, see [`Env::copy_to_fd`].
+    ///
+    /// # Safety
+    ///
+    /// The file descriptor must be valid and opened in write mode.
+    pub unsafe fn copy_to_fd(&self, fd: ffi::mdb_filehandle_t, option: CompactionOption) -> Result<()> {
+        self.0.copy_to_fd(fd, option)
+    }
 
-        Ok(())
+    /// Streams a snapshot of the environment into the given writer, see [`Env::copy_to_writer`].
+    #[cfg(unix)]
+    pub fn copy_to_writer<W: io::Write>(&self, writer: W, option: CompactionOption) -> Result<u64> {
+        self.0.copy_to_writer(writer, option)
+    }
+
+    /// Writes the given databases in the `mdb_dump` format, see [`Env::dump`].
+    pub fn dump<W: io::Write>(
+        &self,
+        writer: W,
+        databases: &[Option<&str>],
+        format: DumpFormat,
+    ) -> Result<()> {
+        self.0.dump(writer, databases, format)
     }
 
     /// Returns the canonicalized path where this env lives.
     pub fn path(&self) -> &Path {
-        &self.0.path
+        self.0.path()
     }
 
-    /// Returns an `EnvClosingEvent` that can be used to wait for the closing event,
-    /// multiple threads can wait on this event.
-    ///
-    /// Make sure that you drop all the copies of `Env`s you have, env closing are triggered
-    /// when all references are dropped, the last one will eventually close the environment.
-    pub fn prepare_for_closing(self) -> EnvClosingEvent {
-        let mut lock = OPENED_ENV.write().unwrap();
-        let env = lock.get_mut(&self.0.path);
+    /// Returns the statistics of the main database, see [`Env::stat`].
+    pub fn stat(&self) -> Result<DatabaseStat> {
+        self.0.stat()
+    }
 
-        match env {
-            None => panic!("cannot find the env that we are trying to close"),
-            Some((env, signal_event)) => {
-                // We remove the env from the global list and replace it with a None.
-                let _env = env.take();
-                let signal_event = signal_event.clone();
+    /// Returns information about this environment, see [`Env::info`].
+    pub fn info(&self) -> Result<EnvInfo> {
+        self.0.info()
+    }
 
-                // we must make sure we release the lock before we drop the env
-                // as the drop of the EnvInner also tries to lock the OPENED_ENV
-                // global and we don't want to trigger a dead-lock.
-                drop(lock);
+    /// Lists the reader slots that are in use, see [`Env::readers`].
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    pub fn readers(&self) -> Result<Vec<ReaderInfo>> {
+        self.0.readers()
+    }
 
-                EnvClosingEvent(signal_event)
-            }
-        }
+    /// Returns an `EnvClosingEvent` that can be used to wait for the closing event,
+    /// see [`Env::prepare_for_closing`].
+    pub fn prepare_for_closing(self) -> EnvClosingEvent {
+        self.0.prepare_for_closing()
     }
 }
 
@@ -541,4 +2727,314 @@ mod tests {
         // Make sure we don't have a reference to the env
         assert!(env_closing_event(Path::new("target").join("close-env.mdb")).is_none());
     }
+
+    #[test]
+    fn resize_with_alive_txns() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+
+        fs::create_dir_all(Path::new("target").join("resize-with-alive-txns.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .open(Path::new("target").join("resize-with-alive-txns.mdb")).unwrap();
+
+        let rtxn = env.read_txn().unwrap();
+        assert!(env.resize(20 * 1024 * 1024).is_err());
+        drop(rtxn);
+
+        let wtxn = env.write_txn().unwrap();
+        assert!(env.resize(20 * 1024 * 1024).is_err());
+        wtxn.commit().unwrap();
+
+        env.resize(20 * 1024 * 1024).unwrap();
+        assert_eq!(env.info().unwrap().map_size, 20 * 1024 * 1024);
+
+        // Without a growth policy the map doesn't grow.
+        assert_eq!(env.grow_map().unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn resize_while_copying() {
+        use std::fs;
+        use std::io::{self, Write};
+        use std::path::Path;
+        use crate::{CompactionOption, Env, EnvOpenOptions};
+        use crate::types::*;
+
+        struct ResizingWriter<'a> {
+            env: &'a Env,
+            resized: bool,
+        }
+
+        impl Write for ResizingWriter<'_> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                // The copier thread is still reading the map, blocked by the full pipe.
+                if !self.resized {
+                    assert!(self.env.resize(20 * 1024 * 1024).is_err());
+                    self.resized = true;
+                }
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        fs::create_dir_all(Path::new("target").join("resize-while-copying.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .open(Path::new("target").join("resize-while-copying.mdb")).unwrap();
+
+        // Much more data than the pipe can hold.
+        let db = env.create_database::<OwnedType<u32>, ByteSlice>(None).unwrap();
+        env.write(|wtxn| {
+            for i in 0..256u32 {
+                db.put(wtxn, &i, &&[0; 4096][..])?;
+            }
+            Ok(())
+        }).unwrap();
+
+        let mut writer = ResizingWriter { env: &env, resized: false };
+        env.copy_to_writer(&mut writer, CompactionOption::Disabled).unwrap();
+        assert!(writer.resized);
+
+        env.resize(20 * 1024 * 1024).unwrap();
+    }
+
+    #[test]
+    fn rejected_create_then_create() {
+        use std::fs;
+        use std::path::Path;
+        use crate::{EnvOpenOptions, Error};
+        use crate::types::*;
+
+        let path = Path::new("target").join("rejected-create-then-create.mdb");
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        let env = EnvOpenOptions::new().max_dbs(10).open(&path).unwrap();
+        env.database_options().types::<Str, Str>().name("dups").dup_sort(true).create().unwrap();
+        env.prepare_for_closing().wait();
+
+        let env = EnvOpenOptions::new().max_dbs(10).open(&path).unwrap();
+        let ret = env.database_options().types::<Str, Str>().name("dups").create();
+        assert!(matches!(ret, Err(Error::InvalidDatabaseFlags)));
+
+        // The handle of the rejected database has been closed and is reused.
+        env.create_database::<Str, OwnedType<u32>>(Some("counts")).unwrap();
+        env.database_options().types::<Str, Str>().name("dups").dup_sort(true).open().unwrap().unwrap();
+    }
+
+    #[test]
+    fn pooled_read_txn_reset_and_renewed() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+
+        fs::create_dir_all(Path::new("target").join("pooled-read-txn-reset-and-renewed.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .read_txn_pool(2)
+            .open(Path::new("target").join("pooled-read-txn-reset-and-renewed.mdb")).unwrap();
+        let pool_len = || env.0.read_txn_pool.as_ref().unwrap().txns.lock().unwrap().len();
+
+        drop(env.read_txn().unwrap());
+        assert_eq!(pool_len(), 1);
+
+        // The transaction taken from the pool goes back to it once reset and renewed.
+        let rtxn = env.read_txn().unwrap();
+        assert_eq!(pool_len(), 0);
+        let rtxn = rtxn.reset().renew().unwrap();
+        drop(rtxn);
+        assert_eq!(pool_len(), 1);
+
+        // Or when it is dropped while reset.
+        drop(env.read_txn().unwrap().reset());
+        assert_eq!(pool_len(), 1);
+    }
+
+    #[test]
+    fn database_names_without_opening() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        let path = Path::new("target").join("database-names-without-opening.mdb");
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        let env = EnvOpenOptions::new().max_dbs(3).open(&path).unwrap();
+        for name in &["a", "b", "c"] {
+            env.create_database::<Str, Str>(Some(name)).unwrap();
+        }
+        env.prepare_for_closing().wait();
+
+        // Listing the databases doesn't use the database slots.
+        let env = EnvOpenOptions::new().max_dbs(1).open(&path).unwrap();
+        let rtxn = env.read_txn().unwrap();
+        assert_eq!(env.database_names(&rtxn).unwrap(), vec!["a", "b", "c"]);
+        rtxn.commit().unwrap();
+
+        env.open_database::<Str, Str>(Some("c")).unwrap().unwrap();
+    }
+
+    #[test]
+    fn copy_and_rename_database_with_typed_txn() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        let path = Path::new("target").join("copy-and-rename-database-with-typed-txn.mdb");
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        let env = EnvOpenOptions::new().max_dbs(10).open(&path).unwrap();
+        let db = env.create_database::<Str, Str>(Some("source")).unwrap();
+
+        struct Typed;
+        let mut wtxn = env.typed_write_txn::<Typed>().unwrap();
+        db.put(&mut wtxn, &"hello", &"world").unwrap();
+        let copy = env.copy_database(&mut wtxn, db, "copy").unwrap();
+        let renamed = env.rename_database(&mut wtxn, db, "renamed").unwrap();
+        assert_eq!(copy.get(&wtxn, &"hello").unwrap(), Some("world"));
+        assert_eq!(renamed.get(&wtxn, &"hello").unwrap(), Some("world"));
+        assert_eq!(env.database_names(&wtxn).unwrap(), vec!["copy", "renamed"]);
+        wtxn.commit().unwrap();
+    }
+
+    #[test]
+    fn delete_database_then_abort() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("delete-database-then-abort.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(10)
+            .open(Path::new("target").join("delete-database-then-abort.mdb")).unwrap();
+
+        let db = env.create_database::<Str, Str>(Some("kept")).unwrap();
+        env.write(|wtxn| db.put(wtxn, &"hello", &"world")).unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        env.delete_database(&mut wtxn, db).unwrap();
+        wtxn.abort().unwrap();
+
+        // The database is kept but the handle has been closed, it must be opened again.
+        let db = env.open_database::<Str, Str>(Some("kept")).unwrap().unwrap();
+        let rtxn = env.read_txn().unwrap();
+        assert_eq!(db.get(&rtxn, &"hello").unwrap(), Some("world"));
+        assert_eq!(env.database_names(&rtxn).unwrap(), vec!["kept"]);
+    }
+
+    #[test]
+    fn durability_last_call_wins() {
+        use std::fs;
+        use std::path::Path;
+        use std::time::Duration;
+        use crate::{Durability, EnvFlag, EnvOpenOptions};
+        use crate::flags::Flags;
+
+        let path = Path::new("target").join("durability-last-call-wins.mdb");
+        fs::create_dir_all(&path).unwrap();
+
+        // A periodic flush without any interval is rejected.
+        let durability = Durability::Periodic { interval: Duration::ZERO, max_unsynced_commits: None };
+        assert!(EnvOpenOptions::new().durability(durability).open(&path).is_err());
+
+        let env = EnvOpenOptions::new().no_sync(true).durability(Durability::Full).open(&path).unwrap();
+        assert!(!env.flags().unwrap().contains(EnvFlag::NoSync));
+        env.prepare_for_closing().wait();
+
+        let env = EnvOpenOptions::new().durability(Durability::Manual).no_meta_sync(true).open(&path).unwrap();
+        assert!(!env.flags().unwrap().contains(EnvFlag::NoSync));
+        assert!(env.flags().unwrap().contains(EnvFlag::NoMetaSync));
+        env.prepare_for_closing().wait();
+
+        // The read-only environments have nothing to flush.
+        let periodic = Durability::Periodic { interval: Duration::from_millis(10), max_unsynced_commits: None };
+        let env = EnvOpenOptions::new().durability(periodic).open_read_only(&path).unwrap();
+        assert!(env.0 .0.flusher.is_none());
+        env.0.prepare_for_closing().wait();
+
+        let mut options = EnvOpenOptions::new();
+        unsafe { options.flag(Flags::MdbRdOnly) };
+        let env = options.durability(periodic).open(&path).unwrap();
+        assert!(env.0.flusher.is_none());
+        env.prepare_for_closing().wait();
+    }
+
+    #[test]
+    fn codec_fingerprints_with_schemas() {
+        use std::fs;
+        use std::path::Path;
+        use crate::{CodecSchema, Database, Env, EnvOpenOptions, Error};
+        use crate::types::*;
+
+        enum Keys {}
+        enum Versioned {}
+
+        impl CodecSchema for Keys {
+            const SCHEMA_ID: u32 = 1;
+        }
+
+        impl CodecSchema for Versioned {
+            const SCHEMA_ID: u32 = 2;
+        }
+
+        fn open(env: &Env, name: &str, schemas: bool) -> Result<Option<Database<Keys, Versioned>>, Error> {
+            let mut options = env.database_options().types::<Keys, Versioned>();
+            options.name(name);
+            if schemas {
+                options.codec_schemas();
+            }
+            options.open()
+        }
+
+        let path = Path::new("target").join("codec-fingerprints-with-schemas.mdb");
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        let env = EnvOpenOptions::new().max_dbs(10).codec_fingerprints(true).open(&path).unwrap();
+        let db = env.database_options().types::<Keys, Versioned>().name("versioned").codec_schemas().create().unwrap();
+        let strings: Database<Str, Str> = env.create_database(Some("strings")).unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        env.copy_database(&mut wtxn, db, "copy").unwrap();
+        env.delete_database(&mut wtxn, strings).unwrap();
+        wtxn.commit().unwrap();
+
+        env.prepare_for_closing().wait();
+        let env = EnvOpenOptions::new().max_dbs(10).codec_fingerprints(true).open(&path).unwrap();
+
+        for name in &["versioned", "copy"] {
+            assert!(matches!(open(&env, name, false), Err(Error::InvalidDatabaseTyping)));
+            assert!(open(&env, name, true).unwrap().is_some());
+        }
+
+        // The fingerprint of a deleted database is removed with it.
+        let _: Database<Str, ByteSlice> = env.create_database(Some("strings")).unwrap();
+    }
+
+    #[test]
+    #[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+    fn parse_reader_lines() {
+        use super::ReaderInfo;
+
+        assert_eq!(ReaderInfo::from_line("    pid     thread     txnid\n"), None);
+        assert_eq!(ReaderInfo::from_line("(no active readers)\n"), None);
+        assert_eq!(
+            ReaderInfo::from_line("     12345 7f1c2b3a4700 42\n"),
+            Some(ReaderInfo { pid: 12345, thread: 0x7f1c2b3a4700, txn_id: Some(42) }),
+        );
+        assert_eq!(
+            ReaderInfo::from_line("        17 1f -\n"),
+            Some(ReaderInfo { pid: 17, thread: 0x1f, txn_id: None }),
+        );
+    }
 }
@@ -0,0 +1,109 @@
+use std::io;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::mdb::error::mdb_result;
+use crate::mdb::ffi;
+use crate::Result;
+
+/// Flushes an environment to disk in a background thread, at a regular interval
+/// or once enough transactions have been committed since the last flush.
+pub(crate) struct Flusher {
+    state: Arc<(Mutex<FlusherState>, Condvar)>,
+    max_unsynced_commits: Option<usize>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+#[derive(Default)]
+struct FlusherState {
+    unsynced_commits: usize,
+    stopped: bool,
+}
+
+/// The environment pointer moved into the flusher thread,
+/// the thread is always stopped before the environment is closed.
+struct EnvPtr(*mut ffi::MDB_env);
+
+unsafe impl Send for EnvPtr {}
+
+impl Flusher {
+    pub(crate) fn spawn(
+        env: *mut ffi::MDB_env,
+        interval: Duration,
+        max_unsynced_commits: Option<usize>,
+    ) -> io::Result<Flusher> {
+        let state = Arc::new((Mutex::new(FlusherState::default()), Condvar::new()));
+        let thread_state = state.clone();
+        let env = EnvPtr(env);
+
+        let handle = thread::Builder::new().name("heed-flusher".to_string()).spawn(move || {
+            let env = env;
+            let (lock, condvar) = &*thread_state;
+            let mut state = lock.lock().unwrap();
+
+            loop {
+                let deadline = Instant::now() + interval;
+                while !state.stopped
+                    && !matches!(max_unsynced_commits, Some(max) if state.unsynced_commits >= max)
+                {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    state = condvar.wait_timeout(state, deadline - now).unwrap().0;
+                }
+
+                if state.stopped {
+                    break;
+                }
+
+                if state.unsynced_commits > 0 {
+                    state.unsynced_commits = 0;
+                    drop(state);
+                    let _ = sync_env(env.0);
+                    state = lock.lock().unwrap();
+                }
+            }
+        })?;
+
+        Ok(Flusher { state, max_unsynced_commits, handle: Mutex::new(Some(handle)) })
+    }
+
+    /// Notifies the flusher that a write transaction has been committed.
+    pub(crate) fn commit_done(&self) {
+        let (lock, condvar) = &*self.state;
+        let mut state = lock.lock().unwrap();
+        state.unsynced_commits += 1;
+
+        if matches!(self.max_unsynced_commits, Some(max) if state.unsynced_commits >= max) {
+            condvar.notify_one();
+        }
+    }
+
+    /// Stops the flusher thread and waits for it, the environment must then be flushed
+    /// a last time. Stopping an already stopped flusher does nothing.
+    pub(crate) fn stop(&self) {
+        let (lock, condvar) = &*self.state;
+        lock.lock().unwrap().stopped = true;
+        condvar.notify_one();
+
+        if let Some(handle) = self.handle.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+pub(crate) fn sync_env(env: *mut ffi::MDB_env) -> Result<()> {
+    unsafe { mdb_result(ffi::mdb_env_sync(env, 1))? }
+
+    Ok(())
+}
+
+#[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
+pub(crate) fn sync_env(env: *mut ffi::MDB_env) -> Result<()> {
+    unsafe { mdb_result(ffi::mdb_env_sync(env))? }
+
+    Ok(())
+}
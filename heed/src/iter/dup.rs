@@ -0,0 +1,281 @@
+use std::marker;
+
+use crate::*;
+
+/// An iterator over all the data items of a single key of a `MDB_DUPSORT` database,
+/// from the lowest to the highest.
+pub struct RoDupIter<'txn, KC, DC> {
+    cursor: RoCursor<'txn>,
+    move_on_first: bool,
+    single_item: bool,
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, KC, DC> RoDupIter<'txn, KC, DC> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> RoDupIter<'txn, KC, DC> {
+        RoDupIter { cursor, move_on_first: true, single_item: false, _phantom: marker::PhantomData }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoDupIter<'txn, KC2, DC2> {
+        RoDupIter {
+            cursor: self.cursor,
+            move_on_first: self.move_on_first,
+            single_item: self.single_item,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoDupIter<'txn, KC2, DC> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoDupIter<'txn, KC, DC2> {
+        self.remap_types::<KC, DC2>()
+    }
+
+    /// Wrap the data bytes into a lazy decoder.
+    pub fn lazily_decode_data(self) -> RoDupIter<'txn, KC, LazyDecode<DC>> {
+        self.remap_types::<KC, LazyDecode<DC>>()
+    }
+}
+
+impl<'txn, KC, DC> Iterator for RoDupIter<'txn, KC, DC>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_first {
+            self.move_on_first = false;
+            match self.cursor.move_on_first_dup() {
+                // A database without MDB_DUPSORT only stores one data item per key.
+                Err(Error::Mdb(MdbError::Incompatible)) => {
+                    self.single_item = true;
+                    self.cursor.current()
+                }
+                result => result,
+            }
+        } else if self.single_item {
+            Ok(None)
+        } else {
+            self.cursor.move_on_next_dup()
+        };
+
+        match result {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// An iterator over all the data items of a single key of a `MDB_DUPSORT` database,
+/// from the highest to the lowest.
+pub struct RoRevDupIter<'txn, KC, DC> {
+    cursor: RoCursor<'txn>,
+    move_on_last: bool,
+    single_item: bool,
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, KC, DC> RoRevDupIter<'txn, KC, DC> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> RoRevDupIter<'txn, KC, DC> {
+        RoRevDupIter { cursor, move_on_last: true, single_item: false, _phantom: marker::PhantomData }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoRevDupIter<'txn, KC2, DC2> {
+        RoRevDupIter {
+            cursor: self.cursor,
+            move_on_last: self.move_on_last,
+            single_item: self.single_item,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoRevDupIter<'txn, KC2, DC> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoRevDupIter<'txn, KC, DC2> {
+        self.remap_types::<KC, DC2>()
+    }
+
+    /// Wrap the data bytes into a lazy decoder.
+    pub fn lazily_decode_data(self) -> RoRevDupIter<'txn, KC, LazyDecode<DC>> {
+        self.remap_types::<KC, LazyDecode<DC>>()
+    }
+}
+
+impl<'txn, KC, DC> Iterator for RoRevDupIter<'txn, KC, DC>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_last {
+            self.move_on_last = false;
+            match self.cursor.move_on_last_dup() {
+                // A database without MDB_DUPSORT only stores one data item per key.
+                Err(Error::Mdb(MdbError::Incompatible)) => {
+                    self.single_item = true;
+                    self.cursor.current()
+                }
+                result => result,
+            }
+        } else if self.single_item {
+            Ok(None)
+        } else {
+            self.cursor.move_on_prev_dup()
+        };
+
+        match result {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// An iterator over the distinct keys of a `MDB_DUPSORT` database,
+/// returning the lowest data item of every key.
+pub struct RoNoDupIter<'txn, KC, DC> {
+    cursor: RoCursor<'txn>,
+    move_on_first: bool,
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, KC, DC> RoNoDupIter<'txn, KC, DC> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> RoNoDupIter<'txn, KC, DC> {
+        RoNoDupIter { cursor, move_on_first: true, _phantom: marker::PhantomData }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoNoDupIter<'txn, KC2, DC2> {
+        RoNoDupIter {
+            cursor: self.cursor,
+            move_on_first: self.move_on_first,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoNoDupIter<'txn, KC2, DC> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoNoDupIter<'txn, KC, DC2> {
+        self.remap_types::<KC, DC2>()
+    }
+
+    /// Wrap the data bytes into a lazy decoder.
+    pub fn lazily_decode_data(self) -> RoNoDupIter<'txn, KC, LazyDecode<DC>> {
+        self.remap_types::<KC, LazyDecode<DC>>()
+    }
+}
+
+impl<'txn, KC, DC> Iterator for RoNoDupIter<'txn, KC, DC>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_first {
+            self.move_on_first = false;
+            self.cursor.move_on_first()
+        } else {
+            self.cursor.move_on_next_nodup()
+        };
+
+        match result {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A reversed iterator over the distinct keys of a `MDB_DUPSORT` database,
+/// returning the highest data item of every key.
+pub struct RoRevNoDupIter<'txn, KC, DC> {
+    cursor: RoCursor<'txn>,
+    move_on_last: bool,
+    _phantom: marker::PhantomData<(KC, DC)>,
+}
+
+impl<'txn, KC, DC> RoRevNoDupIter<'txn, KC, DC> {
+    pub(crate) fn new(cursor: RoCursor<'txn>) -> RoRevNoDupIter<'txn, KC, DC> {
+        RoRevNoDupIter { cursor, move_on_last: true, _phantom: marker::PhantomData }
+    }
+
+    /// Change the codec types of this iterator, specifying the codecs.
+    pub fn remap_types<KC2, DC2>(self) -> RoRevNoDupIter<'txn, KC2, DC2> {
+        RoRevNoDupIter {
+            cursor: self.cursor,
+            move_on_last: self.move_on_last,
+            _phantom: marker::PhantomData,
+        }
+    }
+
+    /// Change the key codec type of this iterator, specifying the new codec.
+    pub fn remap_key_type<KC2>(self) -> RoRevNoDupIter<'txn, KC2, DC> {
+        self.remap_types::<KC2, DC>()
+    }
+
+    /// Change the data codec type of this iterator, specifying the new codec.
+    pub fn remap_data_type<DC2>(self) -> RoRevNoDupIter<'txn, KC, DC2> {
+        self.remap_types::<KC, DC2>()
+    }
+
+    /// Wrap the data bytes into a lazy decoder.
+    pub fn lazily_decode_data(self) -> RoRevNoDupIter<'txn, KC, LazyDecode<DC>> {
+        self.remap_types::<KC, LazyDecode<DC>>()
+    }
+}
+
+impl<'txn, KC, DC> Iterator for RoRevNoDupIter<'txn, KC, DC>
+where
+    KC: BytesDecode<'txn>,
+    DC: BytesDecode<'txn>,
+{
+    type Item = Result<(KC::DItem, DC::DItem)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if self.move_on_last {
+            self.move_on_last = false;
+            self.cursor.move_on_last()
+        } else {
+            self.cursor.move_on_prev_nodup()
+        };
+
+        match result {
+            Ok(Some((key, data))) => match (KC::bytes_decode(key), DC::bytes_decode(data)) {
+                (Ok(key), Ok(data)) => Some(Ok((key, data))),
+                (Err(e), _) | (_, Err(e)) => Some(Err(Error::Decoding(e))),
+            },
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
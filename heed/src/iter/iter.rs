@@ -18,7 +18,7 @@ impl<'txn, KC, DC> RoIter<'txn, KC, DC> {
         RoIter {
             cursor: self.cursor,
             move_on_first: self.move_on_first,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -117,8 +117,8 @@ impl<'txn, KC, DC> RwIter<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -131,8 +131,8 @@ impl<'txn, KC, DC> RwIter<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.append(&key_bytes, &data_bytes)
     }
 
@@ -141,7 +141,7 @@ impl<'txn, KC, DC> RwIter<'txn, KC, DC> {
         RwIter {
             cursor: self.cursor,
             move_on_first: self.move_on_first,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -226,7 +226,7 @@ impl<'txn, KC, DC> RoRevIter<'txn, KC, DC> {
         RoRevIter {
             cursor: self.cursor,
             move_on_last: self.move_on_last,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -325,8 +325,8 @@ impl<'txn, KC, DC> RwRevIter<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -339,8 +339,8 @@ impl<'txn, KC, DC> RwRevIter<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.append(&key_bytes, &data_bytes)
     }
 
@@ -349,7 +349,7 @@ impl<'txn, KC, DC> RwRevIter<'txn, KC, DC> {
         RwRevIter {
             cursor: self.cursor,
             move_on_last: self.move_on_last,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -1,7 +1,10 @@
+mod dup;
+#[allow(clippy::module_inception)]
 mod iter;
 mod range;
 mod prefix;
 
+pub use self::dup::{RoDupIter, RoRevDupIter, RoNoDupIter, RoRevNoDupIter};
 pub use self::iter::{RoIter, RoRevIter, RwIter, RwRevIter};
 pub use self::range::{RoRange, RoRevRange, RwRange, RwRevRange};
 pub use self::prefix::{RoPrefix, RoRevPrefix, RwPrefix, RwRevPrefix};
@@ -348,4 +351,211 @@ mod tests {
 
         wtxn.abort().unwrap();
     }
+
+    #[test]
+    fn dup_iters() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("dup_iters.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(3000)
+            .open(Path::new("target").join("dup_iters.mdb")).unwrap();
+        let db = env.create_dup_database::<Str, Str>(Some("dups")).unwrap();
+
+        // Create multiple keys with multiple values each...
+        let mut wtxn = env.write_txn().unwrap();
+        db.put(&mut wtxn, &"a", &"2").unwrap();
+        db.put(&mut wtxn, &"a", &"1").unwrap();
+        db.put(&mut wtxn, &"b", &"1").unwrap();
+        db.put(&mut wtxn, &"c", &"3").unwrap();
+        db.put(&mut wtxn, &"c", &"1").unwrap();
+        db.put(&mut wtxn, &"c", &"2").unwrap();
+
+        // Lets check that every data item is visible by the classic iterator.
+        assert_eq!(db.len(&wtxn).unwrap(), 6);
+
+        let mut iter = db.iter_nodup(&wtxn).unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("a", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("b", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("c", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        let mut iter = db.rev_iter_nodup(&wtxn).unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("c", "3")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("b", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("a", "2")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        let mut iter = db.get_duplicates(&wtxn, &"b").unwrap().unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("b", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        let mut iter = db.rev_get_duplicates(&wtxn, &"c").unwrap().unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("c", "3")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("c", "2")));
+        assert_eq!(iter.next().transpose().unwrap(), Some(("c", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        assert!(db.delete_one(&mut wtxn, &"c", &"2").unwrap());
+        assert!(db.delete(&mut wtxn, &"a").unwrap());
+
+        let rets: Vec<_> = db.iter(&wtxn).unwrap().collect::<crate::Result<_>>().unwrap();
+        assert_eq!(rets, vec![("b", "1"), ("c", "1"), ("c", "3")]);
+
+        wtxn.abort().unwrap();
+    }
+
+    #[test]
+    fn dup_iters_without_dupsort() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("dup_iters_without_dupsort.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(3000)
+            .open(Path::new("target").join("dup_iters_without_dupsort.mdb")).unwrap();
+        let db = env.create_database::<Str, Str>(Some("no-dups")).unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        db.put(&mut wtxn, &"a", &"1").unwrap();
+        db.put(&mut wtxn, &"b", &"2").unwrap();
+
+        // Lets check that the single data item of the key is returned.
+        let mut iter = db.get_duplicates(&wtxn, &"a").unwrap().unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("a", "1")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        let mut iter = db.rev_get_duplicates(&wtxn, &"b").unwrap().unwrap();
+        assert_eq!(iter.next().transpose().unwrap(), Some(("b", "2")));
+        assert_eq!(iter.next().transpose().unwrap(), None);
+        drop(iter);
+
+        wtxn.abort().unwrap();
+    }
+
+    #[test]
+    fn range_with_custom_comparator() {
+        use std::cmp::Ordering;
+        use std::fs;
+        use std::ops::Bound;
+        use std::path::Path;
+        use crate::{Comparator, EnvOpenOptions};
+        use crate::types::*;
+
+        enum ReverseCmp {}
+
+        impl Comparator for ReverseCmp {
+            fn compare(a: &[u8], b: &[u8]) -> Ordering {
+                b.cmp(a)
+            }
+        }
+
+        fs::create_dir_all(Path::new("target").join("range_with_custom_comparator.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(3000)
+            .open(Path::new("target").join("range_with_custom_comparator.mdb")).unwrap();
+        let db = env.database_options()
+            .types::<Str, Unit>()
+            .key_comparator::<ReverseCmp>()
+            .create()
+            .unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        for key in &["a", "b", "c", "d", "e"] {
+            db.put(&mut wtxn, key, &()).unwrap();
+        }
+
+        let range = (Bound::Included("d"), Bound::Included("b"));
+        let keys: Vec<_> = db.range(&wtxn, range).unwrap().map(|r| r.unwrap().0).collect();
+        assert_eq!(keys, vec!["d", "c", "b"]);
+
+        let range = (Bound::Excluded("d"), Bound::Excluded("a"));
+        let keys: Vec<_> = db.range(&wtxn, range).unwrap().map(|r| r.unwrap().0).collect();
+        assert_eq!(keys, vec!["c", "b"]);
+
+        let range = (Bound::Excluded("d"), Bound::Unbounded);
+        let last = db.range(&wtxn, range).unwrap().last().transpose().unwrap();
+        assert_eq!(last, Some(("a", ())));
+
+        let range = (Bound::Included("c"), Bound::Excluded("a"));
+        let rev: Vec<_> = db.rev_range(&wtxn, range).unwrap().map(|r| r.unwrap().0).collect();
+        assert_eq!(rev, vec!["b", "c"]);
+
+        assert_eq!(db.get_lower_than_or_equal_to(&wtxn, &"c").unwrap(), Some(("c", ())));
+        assert_eq!(db.get_greater_than(&wtxn, &"c").unwrap(), Some(("b", ())));
+
+        // The keys sharing a prefix are not necessarily sorted together.
+        assert!(db.prefix_iter(&wtxn, &"a").is_err());
+        assert!(db.rev_prefix_iter(&wtxn, &"a").is_err());
+        assert!(db.prefix_iter_mut(&mut wtxn, &"a").is_err());
+        assert!(db.rev_prefix_iter_mut(&mut wtxn, &"a").is_err());
+
+        wtxn.abort().unwrap();
+    }
+
+    #[test]
+    fn prefix_iter_with_key_flags() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("prefix_iter_with_key_flags.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .max_dbs(3000)
+            .open(Path::new("target").join("prefix_iter_with_key_flags.mdb")).unwrap();
+        let reversed = env.database_options()
+            .types::<Str, Unit>()
+            .name("reverse-key")
+            .reverse_key(true)
+            .create()
+            .unwrap();
+        let integers = env.database_options()
+            .types::<OwnedType<u32>, Unit>()
+            .name("integer-key")
+            .integer_key(true)
+            .create()
+            .unwrap();
+
+        let mut wtxn = env.write_txn().unwrap();
+        for key in &["ab", "ba", "bb"] {
+            reversed.put(&mut wtxn, key, &()).unwrap();
+        }
+        for key in &[1, 256, 257] {
+            integers.put(&mut wtxn, key, &()).unwrap();
+        }
+
+        // The keys sharing a prefix are not sorted together.
+        assert!(reversed.prefix_iter(&wtxn, &"b").is_err());
+        assert!(reversed.rev_prefix_iter(&wtxn, &"b").is_err());
+        assert!(reversed.prefix_iter_mut(&mut wtxn, &"b").is_err());
+        assert!(reversed.rev_prefix_iter_mut(&mut wtxn, &"b").is_err());
+
+        assert!(integers.prefix_iter(&wtxn, &1).is_err());
+        assert!(integers.rev_prefix_iter(&wtxn, &1).is_err());
+        assert!(integers.prefix_iter_mut(&mut wtxn, &1).is_err());
+        assert!(integers.rev_prefix_iter_mut(&mut wtxn, &1).is_err());
+
+        // The other iterators still work.
+        let keys: Vec<_> = reversed.iter(&wtxn).unwrap().map(|r| r.unwrap().0).collect();
+        assert_eq!(keys, vec!["ba", "ab", "bb"]);
+        let keys: Vec<_> = integers.iter(&wtxn).unwrap().map(|r| r.unwrap().0).collect();
+        assert_eq!(keys, vec![1, 256, 257]);
+
+        wtxn.abort().unwrap();
+    }
 }
@@ -35,7 +35,7 @@ impl<'txn, KC, DC> RoPrefix<'txn, KC, DC> {
             cursor: self.cursor,
             prefix: self.prefix,
             move_on_first: self.move_on_first,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -137,8 +137,8 @@ impl<'txn, KC, DC> RwPrefix<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -148,7 +148,7 @@ impl<'txn, KC, DC> RwPrefix<'txn, KC, DC> {
             cursor: self.cursor,
             prefix: self.prefix,
             move_on_first: self.move_on_first,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -247,7 +247,7 @@ impl<'txn, KC, DC> RoRevPrefix<'txn, KC, DC> {
             cursor: self.cursor,
             prefix: self.prefix,
             move_on_last: self.move_on_last,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -351,8 +351,8 @@ impl<'txn, KC, DC> RwRevPrefix<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -362,7 +362,7 @@ impl<'txn, KC, DC> RwRevPrefix<'txn, KC, DC> {
             cursor: self.cursor,
             prefix: self.prefix,
             move_on_last: self.move_on_last,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
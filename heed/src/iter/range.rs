@@ -1,9 +1,9 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::marker;
 use std::ops::Bound;
 
 use crate::*;
-use super::{advance_key, retreat_key};
 
 fn move_on_range_end<'txn>(
     cursor: &mut RoCursor<'txn>,
@@ -13,7 +13,9 @@ fn move_on_range_end<'txn>(
     match end_bound {
         Bound::Included(end) => {
             match cursor.move_on_key_greater_than_or_equal_to(end) {
-                Ok(Some((key, data))) if key == &end[..] => Ok(Some((key, data))),
+                Ok(Some((key, data))) if cursor.compare_keys(key, end) == Ordering::Equal => {
+                    Ok(Some((key, data)))
+                },
                 Ok(_) => cursor.move_on_prev(),
                 Err(e) => Err(e),
             }
@@ -29,7 +31,7 @@ fn move_on_range_end<'txn>(
 
 fn move_on_range_start<'txn>(
     cursor: &mut RoCursor<'txn>,
-    start_bound: &mut Bound<Vec<u8>>,
+    start_bound: &Bound<Vec<u8>>,
 ) -> Result<Option<(&'txn [u8], &'txn [u8])>>
 {
     match start_bound {
@@ -37,10 +39,12 @@ fn move_on_range_start<'txn>(
             cursor.move_on_key_greater_than_or_equal_to(start)
         },
         Bound::Excluded(start) => {
-            advance_key(start);
-            let result = cursor.move_on_key_greater_than_or_equal_to(start);
-            retreat_key(start);
-            result
+            match cursor.move_on_key_greater_than_or_equal_to(start) {
+                Ok(Some((key, _))) if cursor.compare_keys(key, start) == Ordering::Equal => {
+                    cursor.move_on_next_nodup()
+                },
+                result => result,
+            }
         },
         Bound::Unbounded => cursor.move_on_first(),
     }
@@ -77,7 +81,7 @@ impl<'txn, KC, DC> RoRange<'txn, KC, DC> {
             move_on_start: self.move_on_start,
             start_bound: self.start_bound,
             end_bound: self.end_bound,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -107,7 +111,7 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         let result = if self.move_on_start {
             self.move_on_start = false;
-            move_on_range_start(&mut self.cursor, &mut self.start_bound)
+            move_on_range_start(&mut self.cursor, &self.start_bound)
         } else {
             self.cursor.move_on_next()
         };
@@ -115,8 +119,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.end_bound {
-                    Bound::Included(end) => key <= end,
-                    Bound::Excluded(end) => key < end,
+                    Bound::Included(end) => self.cursor.compare_keys(key, end) != Ordering::Greater,
+                    Bound::Excluded(end) => self.cursor.compare_keys(key, end) == Ordering::Less,
                     Bound::Unbounded => true,
                 };
 
@@ -150,8 +154,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.start_bound {
-                    Bound::Included(start) => key >= start,
-                    Bound::Excluded(start) => key > start,
+                    Bound::Included(start) => self.cursor.compare_keys(key, start) != Ordering::Less,
+                    Bound::Excluded(start) => self.cursor.compare_keys(key, start) == Ordering::Greater,
                     Bound::Unbounded => true,
                 };
 
@@ -203,8 +207,8 @@ impl<'txn, KC, DC> RwRange<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -215,7 +219,7 @@ impl<'txn, KC, DC> RwRange<'txn, KC, DC> {
             move_on_start: self.move_on_start,
             start_bound: self.start_bound,
             end_bound: self.end_bound,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -245,7 +249,7 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         let result = if self.move_on_start {
             self.move_on_start = false;
-            move_on_range_start(&mut self.cursor, &mut self.start_bound)
+            move_on_range_start(&mut self.cursor, &self.start_bound)
         } else {
             self.cursor.move_on_next()
         };
@@ -253,8 +257,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match self.end_bound {
-                    Bound::Included(ref end) => key <= end,
-                    Bound::Excluded(ref end) => key < end,
+                    Bound::Included(ref end) => self.cursor.compare_keys(key, end) != Ordering::Greater,
+                    Bound::Excluded(ref end) => self.cursor.compare_keys(key, end) == Ordering::Less,
                     Bound::Unbounded => true,
                 };
 
@@ -288,8 +292,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.start_bound {
-                    Bound::Included(start) => key >= start,
-                    Bound::Excluded(start) => key > start,
+                    Bound::Included(start) => self.cursor.compare_keys(key, start) != Ordering::Less,
+                    Bound::Excluded(start) => self.cursor.compare_keys(key, start) == Ordering::Greater,
                     Bound::Unbounded => true,
                 };
 
@@ -339,7 +343,7 @@ impl<'txn, KC, DC> RoRevRange<'txn, KC, DC> {
             move_on_end: self.move_on_end,
             start_bound: self.start_bound,
             end_bound: self.end_bound,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -377,8 +381,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.start_bound {
-                    Bound::Included(start) => key >= start,
-                    Bound::Excluded(start) => key > start,
+                    Bound::Included(start) => self.cursor.compare_keys(key, start) != Ordering::Less,
+                    Bound::Excluded(start) => self.cursor.compare_keys(key, start) == Ordering::Greater,
                     Bound::Unbounded => true,
                 };
 
@@ -398,10 +402,10 @@ where
 
     fn last(mut self) -> Option<Self::Item> {
         let result = if self.move_on_end {
-            move_on_range_start(&mut self.cursor, &mut self.start_bound)
+            move_on_range_start(&mut self.cursor, &self.start_bound)
         } else {
             let current = self.cursor.current();
-            let start = move_on_range_start(&mut self.cursor, &mut self.start_bound);
+            let start = move_on_range_start(&mut self.cursor, &self.start_bound);
             match (current, start) {
                 (Ok(Some((ckey, _))), Ok(Some((key, data)))) if ckey != key => {
                     Ok(Some((key, data)))
@@ -414,8 +418,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.end_bound {
-                    Bound::Included(end) => key <= end,
-                    Bound::Excluded(end) => key < end,
+                    Bound::Included(end) => self.cursor.compare_keys(key, end) != Ordering::Greater,
+                    Bound::Excluded(end) => self.cursor.compare_keys(key, end) == Ordering::Less,
                     Bound::Unbounded => true,
                 };
 
@@ -467,8 +471,8 @@ impl<'txn, KC, DC> RwRevRange<'txn, KC, DC> {
         KC: BytesEncode,
         DC: BytesEncode,
     {
-        let key_bytes: Cow<[u8]> = KC::bytes_encode(&key).map_err(Error::Encoding)?;
-        let data_bytes: Cow<[u8]> = DC::bytes_encode(&data).map_err(Error::Encoding)?;
+        let key_bytes: Cow<[u8]> = KC::bytes_encode(key).map_err(Error::Encoding)?;
+        let data_bytes: Cow<[u8]> = DC::bytes_encode(data).map_err(Error::Encoding)?;
         self.cursor.put_current(&key_bytes, &data_bytes)
     }
 
@@ -479,7 +483,7 @@ impl<'txn, KC, DC> RwRevRange<'txn, KC, DC> {
             move_on_end: self.move_on_end,
             start_bound: self.start_bound,
             end_bound: self.end_bound,
-            _phantom: marker::PhantomData::default(),
+            _phantom: marker::PhantomData,
         }
     }
 
@@ -517,8 +521,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.start_bound {
-                    Bound::Included(start) => key >= start,
-                    Bound::Excluded(start) => key > start,
+                    Bound::Included(start) => self.cursor.compare_keys(key, start) != Ordering::Less,
+                    Bound::Excluded(start) => self.cursor.compare_keys(key, start) == Ordering::Greater,
                     Bound::Unbounded => true,
                 };
 
@@ -538,10 +542,10 @@ where
 
     fn last(mut self) -> Option<Self::Item> {
         let result = if self.move_on_end {
-            move_on_range_start(&mut self.cursor, &mut self.start_bound)
+            move_on_range_start(&mut self.cursor, &self.start_bound)
         } else {
             let current = self.cursor.current();
-            let start = move_on_range_start(&mut self.cursor, &mut self.start_bound);
+            let start = move_on_range_start(&mut self.cursor, &self.start_bound);
             match (current, start) {
                 (Ok(Some((ckey, _))), Ok(Some((key, data)))) if ckey != key => {
                     Ok(Some((key, data)))
@@ -554,8 +558,8 @@ where
         match result {
             Ok(Some((key, data))) => {
                 let must_be_returned = match &self.end_bound {
-                    Bound::Included(end) => key <= end,
-                    Bound::Excluded(end) => key < end,
+                    Bound::Included(end) => self.cursor.compare_keys(key, end) != Ordering::Greater,
+                    Bound::Excluded(end) => self.cursor.compare_keys(key, end) == Ordering::Less,
                     Bound::Unbounded => true,
                 };
 
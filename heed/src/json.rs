@@ -0,0 +1,324 @@
+use std::error::Error as StdError;
+use std::io::{self, BufRead, Write};
+use std::result;
+
+use bytemuck::Pod;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::types::*;
+use crate::{BytesDecode, BytesEncode, Database, Error, Result, RoTxn, RwTxn};
+
+/// A codec that can render the bytes it decodes as JSON and read them back,
+/// it is used to export and import a [`Database`] as JSON Lines.
+///
+/// The codecs that decode to [`Serialize`] types are rendered through serde,
+/// the raw bytes of a [`ByteSlice`] are rendered as a base64 string.
+pub trait JsonCodec {
+    /// Decodes the bytes and renders them as a JSON value.
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>>;
+
+    /// Reads the JSON value and encodes it into bytes.
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>>;
+}
+
+fn serialize_decoded<'a, C>(bytes: &'a [u8]) -> result::Result<Value, Box<dyn StdError>>
+where
+    C: BytesDecode<'a>,
+    C::DItem: Serialize,
+{
+    let item = C::bytes_decode(bytes)?;
+    serde_json::to_value(item).map_err(Into::into)
+}
+
+fn encode_deserialized<C>(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>>
+where
+    C: BytesEncode,
+    C::EItem: DeserializeOwned + Sized,
+{
+    let item: C::EItem = serde_json::from_value(value)?;
+    C::bytes_encode(&item).map(|bytes| bytes.into_owned())
+}
+
+impl JsonCodec for Str<'_> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Str>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        let string: String = serde_json::from_value(value)?;
+        Ok(string.into_bytes())
+    }
+}
+
+impl JsonCodec for Unit {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Unit>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Unit>(value)
+    }
+}
+
+impl JsonCodec for ByteSlice<'_> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        Ok(Value::String(base64_encode(bytes)))
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        match value {
+            Value::String(string) => base64_decode(&string).ok_or_else(|| "invalid base64".into()),
+            _ => Err("expected a base64 string".into()),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> JsonCodec for SerdeJson<T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Self>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Self>(value)
+    }
+}
+
+#[cfg(feature = "serde-bincode")]
+impl<T: Serialize + DeserializeOwned> JsonCodec for SerdeBincode<T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Self>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Self>(value)
+    }
+}
+
+impl<T: Pod + Serialize + DeserializeOwned> JsonCodec for OwnedType<T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Self>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Self>(value)
+    }
+}
+
+impl<T: Pod + Serialize + DeserializeOwned> JsonCodec for CowType<T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Self>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Self>(value)
+    }
+}
+
+impl<T: Pod + Serialize + DeserializeOwned> JsonCodec for UnalignedType<T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<Self>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        encode_deserialized::<Self>(value)
+    }
+}
+
+impl<T: Pod + Serialize + DeserializeOwned> JsonCodec for OwnedSlice<'_, T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<OwnedSlice<T>>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        let items: Vec<T> = serde_json::from_value(value)?;
+        OwnedSlice::<T>::bytes_encode(&&items[..]).map(|bytes| bytes.into_owned())
+    }
+}
+
+impl<T: Pod + Serialize + DeserializeOwned> JsonCodec for CowSlice<'_, T> {
+    fn bytes_to_json(bytes: &[u8]) -> result::Result<Value, Box<dyn StdError>> {
+        serialize_decoded::<CowSlice<T>>(bytes)
+    }
+
+    fn json_to_bytes(value: Value) -> result::Result<Vec<u8>, Box<dyn StdError>> {
+        let items: Vec<T> = serde_json::from_value(value)?;
+        CowSlice::<T>::bytes_encode(&&items[..]).map(|bytes| bytes.into_owned())
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the bytes in the standard base64 alphabet, with padding.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3f;
+                output.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+/// Decodes a string encoded with [`base64_encode`], returns `None` if it is invalid.
+fn base64_decode(string: &str) -> Option<Vec<u8>> {
+    let string = string.as_bytes();
+    if string.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(string.len() / 4 * 3);
+    for (i, chunk) in string.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != string.len() / 4) {
+            return None;
+        }
+
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+            n = n << 6 | value as u32;
+        }
+        n <<= 6 * padding as u32;
+
+        output.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+
+    Some(output)
+}
+
+impl<KC, DC> Database<KC, DC>
+where
+    KC: JsonCodec,
+    DC: JsonCodec,
+{
+    /// Writes all the entries of this database into the writer as JSON Lines,
+    /// one `{"key": ..., "value": ...}` object per line, rendered by the [`JsonCodec`]s
+    /// of the database. Returns the number of entries written.
+    ///
+    /// ```
+    /// # use std::fs;
+    /// # use std::path::Path;
+    /// # use heed::EnvOpenOptions;
+    /// use heed::Database;
+    /// use heed::types::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fs::create_dir_all(Path::new("target").join("json-export.mdb"))?;
+    /// # let env = EnvOpenOptions::new()
+    /// #     .map_size(10 * 1024 * 1024) // 10MB
+    /// #     .max_dbs(3000)
+    /// #     .open(Path::new("target").join("json-export.mdb"))?;
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Color { red: u8, green: u8, blue: u8 }
+    ///
+    /// let db: Database<Str, SerdeBincode<Color>> = env.create_database(Some("colors"))?;
+    /// let raw: Database<Str, ByteSlice> = env.create_database(Some("raw"))?;
+    ///
+    /// let mut wtxn = env.write_txn()?;
+    /// db.clear(&mut wtxn)?;
+    /// db.put(&mut wtxn, &"teal", &Color { red: 0, green: 128, blue: 128 })?;
+    /// raw.clear(&mut wtxn)?;
+    /// raw.put(&mut wtxn, &"bytes", &&[0, 1, 2, 255][..])?;
+    ///
+    /// let mut colors = Vec::new();
+    /// db.export_json(&wtxn, &mut colors)?;
+    /// assert_eq!(colors, &b"{\"key\":\"teal\",\"value\":{\"blue\":128,\"green\":128,\"red\":0}}\n"[..]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// raw.export_json(&wtxn, &mut bytes)?;
+    /// assert_eq!(bytes, &b"{\"key\":\"bytes\",\"value\":\"AAEC/w==\"}\n"[..]);
+    ///
+    /// db.clear(&mut wtxn)?;
+    /// assert_eq!(db.import_json(&mut wtxn, &colors[..])?, 1);
+    /// assert_eq!(db.get(&wtxn, &"teal")?.map(|color| color.green), Some(128));
+    /// wtxn.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn export_json<T, W: Write>(&self, txn: &RoTxn<T>, mut writer: W) -> Result<usize> {
+        let mut count = 0;
+
+        for result in self.remap_types::<ByteSlice, ByteSlice>().iter(txn)? {
+            let (key, data) = result?;
+            let key = KC::bytes_to_json(key).map_err(Error::Decoding)?;
+            let value = DC::bytes_to_json(data).map_err(Error::Decoding)?;
+
+            serde_json::to_writer(&mut writer, &json!({ "key": key, "value": value }))
+                .map_err(io::Error::from)?;
+            writer.write_all(b"\n")?;
+            count += 1;
+        }
+
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// Reads the JSON Lines written by [`Database::export_json`] and puts the entries
+    /// into this database, returns the number of entries imported.
+    ///
+    /// The empty lines are ignored.
+    pub fn import_json<T, R: BufRead>(&self, txn: &mut RwTxn<T>, reader: R) -> Result<usize> {
+        let db = self.remap_types::<ByteSlice, ByteSlice>();
+        let mut count = 0;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid_line = |msg: &str| {
+                let msg = format!("invalid JSON line {}: {}", i + 1, msg);
+                Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+            };
+
+            let mut entry: Value = serde_json::from_str(&line).map_err(|e| invalid_line(&e.to_string()))?;
+            let key = entry.get_mut("key").map(Value::take).ok_or_else(|| invalid_line("missing key"))?;
+            let value = entry.get_mut("value").map(Value::take).ok_or_else(|| invalid_line("missing value"))?;
+
+            let key = KC::json_to_bytes(key).map_err(Error::Encoding)?;
+            let data = DC::json_to_bytes(value).map_err(Error::Encoding)?;
+            db.put(txn, &&key[..], &&data[..])?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_roundtrip() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+
+        for len in 0..32 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 250) as u8).collect();
+            assert_eq!(base64_decode(&base64_encode(&bytes)), Some(bytes));
+        }
+
+        assert_eq!(base64_decode("Zg="), None);
+        assert_eq!(base64_decode("Z==="), None);
+        assert_eq!(base64_decode("Zg==Zm8="), None);
+        assert_eq!(base64_decode("Zm9*"), None);
+    }
+}
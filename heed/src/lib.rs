@@ -49,10 +49,18 @@
 
 mod cursor;
 mod database;
+mod dump;
 mod env;
+mod flusher;
 mod iter;
+#[cfg(feature = "serde-json")]
+mod json;
 mod lazy_decode;
 mod mdb;
+mod metadata;
+pub mod migrations;
+#[cfg(all(feature = "lmdb", not(feature = "mdbx")))]
+mod restore;
 mod txn;
 
 pub use bytemuck;
@@ -61,15 +69,21 @@ pub use heed_types as types;
 use heed_traits as traits;
 
 pub use self::database::Database;
+pub use self::dump::DumpFormat;
 pub use self::env::{CompactionOption, Env, EnvOpenOptions, env_closing_event, EnvClosingEvent};
+pub use self::env::{DatabaseOpenOptions, UntypedData, UntypedKey};
+pub use self::env::{DatabaseStat, Durability, EnvFlag, EnvFlags, EnvInfo, MapGrowth, ReaderInfo, ReadOnlyEnv};
+#[cfg(feature = "serde-json")]
+pub use self::json::JsonCodec;
 pub use self::iter::{RoIter, RoRevIter, RwIter, RwRevIter};
+pub use self::iter::{RoDupIter, RoRevDupIter, RoNoDupIter, RoRevNoDupIter};
 pub use self::iter::{RoPrefix, RoRevPrefix, RwPrefix, RwRevPrefix};
 pub use self::iter::{RoRange, RoRevRange, RwRange, RwRevRange};
 pub use self::lazy_decode::{LazyDecode, Lazy};
 pub use self::mdb::error::Error as MdbError;
 pub use self::mdb::flags;
-pub use self::traits::{BytesDecode, BytesEncode};
-pub use self::txn::{RoTxn, RwTxn};
+pub use self::traits::{BytesDecode, BytesEncode, CodecSchema, Comparator, DefaultComparator};
+pub use self::txn::{ResetRoTxn, RoTxn, RwTxn};
 use self::cursor::{RoCursor, RwCursor};
 use self::mdb::ffi::{into_val, from_val};
 
@@ -87,6 +101,7 @@ pub enum Error {
     Encoding(Box<dyn StdError>),
     Decoding(Box<dyn StdError>),
     InvalidDatabaseTyping,
+    InvalidDatabaseFlags,
     DatabaseClosing,
 }
 
@@ -100,6 +115,9 @@ impl fmt::Display for Error {
             Error::InvalidDatabaseTyping => {
                 f.write_str("database was previously opened with different types")
             },
+            Error::InvalidDatabaseFlags => {
+                f.write_str("database was previously created with different flags or they cannot be used together")
+            },
             Error::DatabaseClosing => {
                 f.write_str("database is in a closing phase, you can't open it at the same time")
             },
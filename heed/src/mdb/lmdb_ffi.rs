@@ -5,31 +5,56 @@ pub use ffi::MDB_dbi as MDB_dbi;
 pub use ffi::MDB_env as MDB_env;
 pub use ffi::mdb_filehandle_t as mdb_filehandle_t;
 pub use ffi::MDB_txn as MDB_txn;
+pub use ffi::MDB_val as MDB_val;
+pub use ffi::MDB_cmp_func as MDB_cmp_func;
 
 pub use ffi::MDB_APPEND as MDB_APPEND;
+pub use ffi::MDB_APPENDDUP as MDB_APPENDDUP;
 pub use ffi::MDB_CP_COMPACT as MDB_CP_COMPACT;
 pub use ffi::MDB_CREATE as MDB_CREATE;
 pub use ffi::MDB_CURRENT as MDB_CURRENT;
+pub use ffi::MDB_DUPFIXED as MDB_DUPFIXED;
+pub use ffi::MDB_DUPSORT as MDB_DUPSORT;
+pub use ffi::MDB_INTEGERDUP as MDB_INTEGERDUP;
+pub use ffi::MDB_INTEGERKEY as MDB_INTEGERKEY;
+pub use ffi::MDB_NODUPDATA as MDB_NODUPDATA;
+pub use ffi::MDB_NOOVERWRITE as MDB_NOOVERWRITE;
 pub use ffi::MDB_RDONLY as MDB_RDONLY;
+pub use ffi::MDB_RESERVE as MDB_RESERVE;
+pub use ffi::MDB_REVERSEDUP as MDB_REVERSEDUP;
+pub use ffi::MDB_REVERSEKEY as MDB_REVERSEKEY;
 
 pub use ffi::mdb_env_close as mdb_env_close;
 pub use ffi::mdb_env_copyfd2 as mdb_env_copy2fd;
 pub use ffi::mdb_env_create as mdb_env_create;
+pub use ffi::mdb_env_get_flags as mdb_env_get_flags;
+pub use ffi::mdb_env_info as mdb_env_info;
 pub use ffi::mdb_env_open as mdb_env_open;
 pub use ffi::mdb_env_set_mapsize as mdb_env_set_mapsize;
 pub use ffi::mdb_env_set_maxdbs as mdb_env_set_maxdbs;
 pub use ffi::mdb_env_set_maxreaders as mdb_env_set_maxreaders;
+pub use ffi::mdb_env_stat as mdb_env_stat;
 pub use ffi::mdb_env_sync as mdb_env_sync;
 
+pub use ffi::mdb_dbi_flags as mdb_dbi_flags;
 pub use ffi::mdb_dbi_open as mdb_dbi_open;
 pub use ffi::mdb_del as mdb_del;
 pub use ffi::mdb_drop as mdb_drop;
 pub use ffi::mdb_get as mdb_get;
 pub use ffi::mdb_put as mdb_put;
 
+pub use ffi::mdb_reader_check as mdb_reader_check;
+pub use ffi::mdb_reader_list as mdb_reader_list;
+pub use ffi::mdb_cmp as mdb_cmp;
+pub use ffi::mdb_dcmp as mdb_dcmp;
+pub use ffi::mdb_set_compare as mdb_set_compare;
+pub use ffi::mdb_set_dupsort as mdb_set_dupsort;
+
 pub use ffi::mdb_txn_abort as mdb_txn_abort;
 pub use ffi::mdb_txn_begin as mdb_txn_begin;
 pub use ffi::mdb_txn_commit as mdb_txn_commit;
+pub use ffi::mdb_txn_renew as mdb_txn_renew;
+pub use ffi::mdb_txn_reset as mdb_txn_reset;
 
 pub use ffi::mdb_cursor_close as mdb_cursor_close;
 pub use ffi::mdb_cursor_del as mdb_cursor_del;
@@ -37,10 +62,9 @@ pub use ffi::mdb_cursor_get as mdb_cursor_get;
 pub use ffi::mdb_cursor_open as mdb_cursor_open;
 pub use ffi::mdb_cursor_put as mdb_cursor_put;
 
-pub use ffi::mdb_env_stat as mdb_env_stat;
 pub use ffi::mdb_stat as mdb_stat;
 pub use ffi::MDB_stat as MDB_Stat;
-
+pub use ffi::MDB_envinfo as MDB_envinfo;
 
 pub mod cursor_op {
     use super::ffi::{self, MDB_cursor_op};
@@ -51,6 +75,13 @@ pub mod cursor_op {
     pub const MDB_PREV: MDB_cursor_op = ffi::MDB_PREV;
     pub const MDB_NEXT: MDB_cursor_op = ffi::MDB_NEXT;
     pub const MDB_GET_CURRENT: MDB_cursor_op = ffi::MDB_GET_CURRENT;
+    pub const MDB_SET_KEY: MDB_cursor_op = ffi::MDB_SET_KEY;
+    pub const MDB_FIRST_DUP: MDB_cursor_op = ffi::MDB_FIRST_DUP;
+    pub const MDB_LAST_DUP: MDB_cursor_op = ffi::MDB_LAST_DUP;
+    pub const MDB_NEXT_DUP: MDB_cursor_op = ffi::MDB_NEXT_DUP;
+    pub const MDB_PREV_DUP: MDB_cursor_op = ffi::MDB_PREV_DUP;
+    pub const MDB_NEXT_NODUP: MDB_cursor_op = ffi::MDB_NEXT_NODUP;
+    pub const MDB_PREV_NODUP: MDB_cursor_op = ffi::MDB_PREV_NODUP;
 }
 
 pub unsafe fn into_val(value: &[u8]) -> ffi::MDB_val {
@@ -63,3 +94,35 @@ pub unsafe fn into_val(value: &[u8]) -> ffi::MDB_val {
 pub unsafe fn from_val<'a>(value: ffi::MDB_val) -> &'a [u8] {
     std::slice::from_raw_parts(value.mv_data as *const u8, value.mv_size)
 }
+
+/// A value of the given size, to reserve space with `MDB_RESERVE`.
+pub fn reserve_val(size: usize) -> ffi::MDB_val {
+    ffi::MDB_val { mv_data: std::ptr::null_mut(), mv_size: size }
+}
+
+/// The space reserved by a put with `MDB_RESERVE`, as a mutable slice.
+///
+/// # Safety
+///
+/// The value must point to the space reserved by a successful put in a write transaction
+/// and the slice must not be used after the next write in this transaction nor after it ends.
+/// The reserved bytes are uninitialized, they must be written before the slice is read.
+pub unsafe fn from_val_mut<'a>(value: ffi::MDB_val) -> &'a mut [u8] {
+    std::slice::from_raw_parts_mut(value.mv_data as *mut u8, value.mv_size)
+}
+
+/// Returns `true` if the key names a database, its node is flagged with `F_SUBDATA`
+/// and not `F_DUPDATA`, like `mdb_dbi_open` checks it.
+///
+/// # Safety
+///
+/// The key must have been returned by a cursor of the unnamed database, it is stored
+/// in a leaf page right after the header of its node, `MDB_node` in `mdb.c`.
+pub unsafe fn is_database_key(key: &[u8]) -> bool {
+    const F_SUBDATA: u16 = 0x02;
+    const F_DUPDATA: u16 = 0x04;
+
+    // The header ends with the `mn_flags` and `mn_ksize` shorts.
+    let flags = std::ptr::read_unaligned(key.as_ptr().sub(4) as *const u16);
+    flags & (F_SUBDATA | F_DUPDATA) == F_SUBDATA
+}
@@ -5,22 +5,38 @@ pub use ffi::MDBX_dbi as MDB_dbi;
 pub use ffi::MDBX_env as MDB_env;
 pub use ffi::mdbx_filehandle_t as mdb_filehandle_t;
 pub use ffi::MDBX_txn as MDB_txn;
+pub use ffi::MDBX_val as MDB_val;
+pub use ffi::MDBX_cmp_func as MDB_cmp_func;
+pub use ffi::{mdbx_pid_t, mdbx_tid_t};
 
 pub use ffi::MDBX_APPEND as MDB_APPEND;
+pub use ffi::MDBX_APPENDDUP as MDB_APPENDDUP;
 pub use ffi::MDBX_CP_COMPACT as MDB_CP_COMPACT;
 pub use ffi::MDBX_CREATE as MDB_CREATE;
 pub use ffi::MDBX_CURRENT as MDB_CURRENT;
+pub use ffi::MDBX_DUPFIXED as MDB_DUPFIXED;
+pub use ffi::MDBX_DUPSORT as MDB_DUPSORT;
+pub use ffi::MDBX_INTEGERDUP as MDB_INTEGERDUP;
+pub use ffi::MDBX_INTEGERKEY as MDB_INTEGERKEY;
+pub use ffi::MDBX_NODUPDATA as MDB_NODUPDATA;
+pub use ffi::MDBX_NOOVERWRITE as MDB_NOOVERWRITE;
 pub use ffi::MDBX_RDONLY as MDB_RDONLY;
+pub use ffi::MDBX_RESERVE as MDB_RESERVE;
+pub use ffi::MDBX_REVERSEDUP as MDB_REVERSEDUP;
+pub use ffi::MDBX_REVERSEKEY as MDB_REVERSEKEY;
 
 pub use ffi::mdbx_env_close as mdb_env_close;
 pub use ffi::mdbx_env_copy2fd as mdb_env_copy2fd;
 pub use ffi::mdbx_env_create as mdb_env_create;
+pub use ffi::mdbx_env_get_flags as mdb_env_get_flags;
+pub use ffi::mdbx_env_info as mdb_env_info;
 pub use ffi::mdbx_env_open as mdb_env_open;
 pub use ffi::mdbx_env_set_mapsize as mdb_env_set_mapsize;
 pub use ffi::mdbx_env_set_maxdbs as mdb_env_set_maxdbs;
 pub use ffi::mdbx_env_set_maxreaders as mdb_env_set_maxreaders;
 pub use ffi::mdbx_env_sync as mdb_env_sync;
 
+pub use ffi::mdbx_dbi_flags as mdb_dbi_flags;
 pub use ffi::mdbx_dbi_open as mdb_dbi_open;
 pub use ffi::mdbx_dbi_sequence;
 pub use ffi::mdbx_del as mdb_del;
@@ -28,9 +44,16 @@ pub use ffi::mdbx_drop as mdb_drop;
 pub use ffi::mdbx_get as mdb_get;
 pub use ffi::mdbx_put as mdb_put;
 
+pub use ffi::mdbx_reader_check as mdb_reader_check;
+pub use ffi::mdbx_reader_list as mdb_reader_list;
+pub use ffi::mdbx_cmp as mdb_cmp;
+pub use ffi::mdbx_dcmp as mdb_dcmp;
+
 pub use ffi::mdbx_txn_abort as mdb_txn_abort;
 pub use ffi::mdbx_txn_begin as mdb_txn_begin;
 pub use ffi::mdbx_txn_commit as mdb_txn_commit;
+pub use ffi::mdbx_txn_renew as mdb_txn_renew;
+pub use ffi::mdbx_txn_reset as mdb_txn_reset;
 
 pub use ffi::mdbx_cursor_close as mdb_cursor_close;
 pub use ffi::mdbx_cursor_del as mdb_cursor_del;
@@ -41,6 +64,7 @@ pub use ffi::mdbx_cursor_put as mdb_cursor_put;
 pub use ffi::mdbx_env_stat as mdb_env_stat;
 pub use ffi::mdbx_dbi_stat as mdb_stat;
 pub use ffi::MDBX_stat as MDB_Stat;
+pub use ffi::MDBX_envinfo as MDB_envinfo;
 
 pub mod cursor_op {
     use super::ffi::MDBX_cursor_op;
@@ -51,6 +75,13 @@ pub mod cursor_op {
     pub const MDB_PREV: MDBX_cursor_op = MDBX_cursor_op::MDBX_PREV;
     pub const MDB_NEXT: MDBX_cursor_op = MDBX_cursor_op::MDBX_NEXT;
     pub const MDB_GET_CURRENT: MDBX_cursor_op = MDBX_cursor_op::MDBX_GET_CURRENT;
+    pub const MDB_SET_KEY: MDBX_cursor_op = MDBX_cursor_op::MDBX_SET_KEY;
+    pub const MDB_FIRST_DUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_FIRST_DUP;
+    pub const MDB_LAST_DUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_LAST_DUP;
+    pub const MDB_NEXT_DUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_NEXT_DUP;
+    pub const MDB_PREV_DUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_PREV_DUP;
+    pub const MDB_NEXT_NODUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_NEXT_NODUP;
+    pub const MDB_PREV_NODUP: MDBX_cursor_op = MDBX_cursor_op::MDBX_PREV_NODUP;
 }
 
 pub unsafe fn into_val(value: &[u8]) -> ffi::MDBX_val {
@@ -63,3 +94,37 @@ pub unsafe fn into_val(value: &[u8]) -> ffi::MDBX_val {
 pub unsafe fn from_val<'a>(value: ffi::MDBX_val) -> &'a [u8] {
     std::slice::from_raw_parts(value.iov_base as *const u8, value.iov_len)
 }
+
+/// A value of the given size, to reserve space with `MDB_RESERVE`.
+pub fn reserve_val(size: usize) -> ffi::MDBX_val {
+    ffi::MDBX_val { iov_base: std::ptr::null_mut(), iov_len: size }
+}
+
+/// The space reserved by a put with `MDB_RESERVE`, as a mutable slice.
+///
+/// # Safety
+///
+/// The value must point to the space reserved by a successful put in a write transaction
+/// and the slice must not be used after the next write in this transaction nor after it ends.
+/// The reserved bytes are uninitialized, they must be written before the slice is read.
+pub unsafe fn from_val_mut<'a>(value: ffi::MDBX_val) -> &'a mut [u8] {
+    std::slice::from_raw_parts_mut(value.iov_base as *mut u8, value.iov_len)
+}
+
+/// Returns `true` if the key names a database, its node is flagged with `F_SUBDATA`
+/// and not `F_DUPDATA`, like `mdbx_dbi_open` checks it.
+///
+/// # Safety
+///
+/// The key must have been returned by a cursor of the unnamed database, it is stored
+/// in a leaf page right after the header of its node, `MDBX_node` in `mdbx.c`.
+pub unsafe fn is_database_key(key: &[u8]) -> bool {
+    const F_SUBDATA: u8 = 0x02;
+    const F_DUPDATA: u8 = 0x04;
+
+    // The `mn_flags` byte is followed by `mn_extra` and `mn_ksize`, or by
+    // `mn_pgno32` on big endian targets, where the header is reversed.
+    let offset = if cfg!(target_endian = "little") { 4 } else { 5 };
+    let flags = *key.as_ptr().sub(offset);
+    flags & (F_SUBDATA | F_DUPDATA) == F_SUBDATA
+}
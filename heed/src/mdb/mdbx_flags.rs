@@ -2,6 +2,7 @@
 #[repr(u32)]
 pub enum Flags {
     MdbNoSubDir = mdbx_sys::MDBX_NOSUBDIR,
+    MdbNoSync = mdbx_sys::MDBX_SAFE_NOSYNC,
     MdbRdOnly = mdbx_sys::MDBX_RDONLY,
     MdbNoMetaSync = mdbx_sys::MDBX_NOMETASYNC,
     MdbWriteMap = mdbx_sys::MDBX_WRITEMAP,
@@ -0,0 +1,114 @@
+use std::any::type_name;
+
+use crate::env::open_dbi;
+use crate::mdb::ffi;
+use crate::types::{ByteSlice, Str};
+use crate::{Database, Env, Error, Result, RoTxn, RwTxn};
+
+/// The name of the database where heed stores its own metadata,
+/// like the schema version of the environment.
+pub const METADATA_DATABASE_NAME: &str = "__heed_metadata";
+
+/// The metadata database, the values are stored as raw bytes.
+pub(crate) type MetadataDatabase = Database<Str<'static>, ByteSlice<'static>>;
+
+/// Creates the metadata database if it doesn't already exist and opens it.
+pub(crate) fn create_metadata_database(env: &Env, wtxn: &mut RwTxn) -> Result<MetadataDatabase> {
+    env.create_database_with_txn(Some(METADATA_DATABASE_NAME), wtxn)
+}
+
+/// Opens the metadata database, without registering it, as the handles
+/// of the databases may be locked when it is needed.
+fn raw_metadata_database<T>(txn: &RoTxn<T>, create: bool) -> Result<Option<MetadataDatabase>> {
+    let flags = if create { ffi::MDB_CREATE } else { 0 };
+    let env_ident = txn.env.env_mut_ptr() as usize;
+    Ok(open_dbi(txn, Some(METADATA_DATABASE_NAME), flags)?.map(|dbi| Database::new(env_ident, dbi)))
+}
+
+/// The identity of the codecs of a database: their type names and
+/// the schema ids they provide, zero when they don't.
+///
+/// The type names are not guaranteed to be the same across compiler versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Fingerprint {
+    key: (&'static str, u32),
+    data: (&'static str, u32),
+}
+
+impl Fingerprint {
+    pub(crate) fn of<KC, DC>(schema_ids: (u32, u32)) -> Fingerprint {
+        Fingerprint {
+            key: (type_name::<KC>(), schema_ids.0),
+            data: (type_name::<DC>(), schema_ids.1),
+        }
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        let (key, key_id) = self.key;
+        let (data, data_id) = self.data;
+        format!("{}#{}\n{}#{}", key, key_id, data, data_id).into_bytes()
+    }
+}
+
+/// The key of the metadata database under which the fingerprint of a database is stored.
+fn fingerprint_key(name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("codecs/{}", name),
+        None => String::from("codecs"),
+    }
+}
+
+/// Verifies that the database has been created with the same codecs, returns `false`
+/// if no fingerprint is stored for this database and an [`Error::InvalidDatabaseTyping`]
+/// if the stored one is different.
+pub(crate) fn check_fingerprint<T>(txn: &RoTxn<T>, name: Option<&str>, fingerprint: Fingerprint) -> Result<bool> {
+    if name == Some(METADATA_DATABASE_NAME) {
+        return Ok(true);
+    }
+
+    let metadata = match raw_metadata_database(txn, false)? {
+        Some(metadata) => metadata,
+        None => return Ok(false),
+    };
+
+    match metadata.get(txn, &fingerprint_key(name).as_str())? {
+        Some(stored) if stored == &fingerprint.to_bytes()[..] => Ok(true),
+        Some(_) => Err(Error::InvalidDatabaseTyping),
+        None => Ok(false),
+    }
+}
+
+/// Verifies the fingerprint of the database, storing it if there is none.
+pub(crate) fn check_or_store_fingerprint<T>(
+    wtxn: &mut RwTxn<T>,
+    name: Option<&str>,
+    fingerprint: Fingerprint,
+) -> Result<()> {
+    if !check_fingerprint(wtxn, name, fingerprint)? {
+        let metadata = raw_metadata_database(wtxn, true)?.unwrap();
+        metadata.put(wtxn, &fingerprint_key(name).as_str(), &&fingerprint.to_bytes()[..])?;
+    }
+    Ok(())
+}
+
+/// Stores the fingerprint of the `src` database, if any, as the one of the `dst` database.
+pub(crate) fn copy_fingerprint<T>(wtxn: &mut RwTxn<T>, src: &str, dst: &str) -> Result<()> {
+    let metadata = match raw_metadata_database(wtxn, false)? {
+        Some(metadata) => metadata,
+        None => return Ok(()),
+    };
+
+    if let Some(fingerprint) = metadata.get(wtxn, &fingerprint_key(Some(src)).as_str())? {
+        let fingerprint = fingerprint.to_vec();
+        metadata.put(wtxn, &fingerprint_key(Some(dst)).as_str(), &&fingerprint[..])?;
+    }
+    Ok(())
+}
+
+/// Removes the fingerprint of the database, returns `true` if there was one.
+pub(crate) fn remove_fingerprint<T>(wtxn: &mut RwTxn<T>, name: Option<&str>) -> Result<bool> {
+    match raw_metadata_database(wtxn, false)? {
+        Some(metadata) => metadata.delete(wtxn, &fingerprint_key(name).as_str()),
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,254 @@
+//! Versioned migrations of the schema of an environment.
+//!
+//! The migration steps are registered in order, each one upgrades the environment from
+//! one version to the next. The current version of the environment is stored in the
+//! reserved metadata database, named [`METADATA_DATABASE_NAME`], a new environment
+//! has the version zero.
+//!
+//! When the steps are given to [`EnvOpenOptions::migrations`], the pending ones are applied
+//! when the environment is opened, in a single write transaction: the readers either see
+//! the environment before or after all the pending steps. The metadata database requires
+//! one of the [`EnvOpenOptions::max_dbs`] named databases.
+//!
+//! The databases must be opened with [`Env::create_database_with_txn`] as no other
+//! transaction can be begun during a step. As a database can only be opened with one set
+//! of codecs in a process, the steps open the databases with their latest codecs and read
+//! or write the previous encodings by changing the codecs with [`Database::remap_types`].
+//!
+//! ```
+//! use std::fs;
+//! use std::path::Path;
+//! use heed::{Database, EnvOpenOptions, Result, RwTxn};
+//! use heed::migrations::{self, Migrations};
+//! use heed::types::*;
+//!
+//! type Ages = Database<Str<'static>, OwnedType<u32>>;
+//!
+//! // The first version stores the ages as strings.
+//! fn create_ages(wtxn: &mut RwTxn) -> Result<()> {
+//!     let db: Ages = wtxn.env().create_database_with_txn(Some("ages"), wtxn)?;
+//!     let old = db.remap_data_type::<Str>();
+//!     old.put(wtxn, &"alice", &"42")?;
+//!     old.put(wtxn, &"bob", &"27")
+//! }
+//!
+//! // The second version stores them as integers.
+//! fn ages_as_integers(wtxn: &mut RwTxn) -> Result<()> {
+//!     let db: Ages = wtxn.env().create_database_with_txn(Some("ages"), wtxn)?;
+//!     let old = db.remap_data_type::<Str>();
+//!
+//!     let mut ages = Vec::new();
+//!     for result in old.iter(wtxn)? {
+//!         let (name, age) = result?;
+//!         ages.push((name.to_string(), age.parse().unwrap()));
+//!     }
+//!
+//!     for (name, age) in ages {
+//!         db.put(wtxn, &name.as_str(), &age)?;
+//!     }
+//!     Ok(())
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! # let _ = fs::remove_dir_all(Path::new("target").join("migrations.mdb"));
+//! fs::create_dir_all(Path::new("target").join("migrations.mdb"))?;
+//! let mut migrations = Migrations::new();
+//! migrations.step(0, 1, create_ages).step(1, 2, ages_as_integers);
+//!
+//! let env = EnvOpenOptions::new()
+//!     .map_size(10 * 1024 * 1024) // 10MB
+//!     .max_dbs(10)
+//!     .migrations(migrations)
+//!     .open(Path::new("target").join("migrations.mdb"))?;
+//!
+//! let db: Ages = env.open_database(Some("ages"))?.unwrap();
+//! let rtxn = env.read_txn()?;
+//! assert_eq!(migrations::schema_version(&rtxn)?, 2);
+//! assert_eq!(db.get(&rtxn, &"alice")?, Some(42));
+//! # Ok(()) }
+//! ```
+//!
+//! [`EnvOpenOptions::migrations`]: crate::EnvOpenOptions::migrations
+//! [`EnvOpenOptions::max_dbs`]: crate::EnvOpenOptions::max_dbs
+//! [`Database::remap_types`]: crate::Database::remap_types
+//! [`Env::create_database_with_txn`]: crate::Env::create_database_with_txn
+
+use std::convert::TryInto;
+use std::io;
+
+use crate::env::open_dbi;
+use crate::metadata::{create_metadata_database, MetadataDatabase};
+use crate::{Env, Error, Result, RoTxn, RwTxn};
+
+pub use crate::metadata::METADATA_DATABASE_NAME;
+
+/// The key of the metadata database under which the schema version is stored.
+const SCHEMA_VERSION_KEY: &str = "schema-version";
+
+/// A migration step, it upgrades the environment in the given write transaction.
+pub type MigrationFn = fn(&mut RwTxn) -> Result<()>;
+
+/// A migration step, from a version to the next one.
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    from: u64,
+    to: u64,
+    apply: MigrationFn,
+}
+
+/// The ordered list of the migration steps of an environment.
+#[derive(Clone, Debug, Default)]
+pub struct Migrations {
+    steps: Vec<Step>,
+}
+
+impl Migrations {
+    /// Creates an empty list of migrations, the steps are registered with [`Migrations::step`].
+    pub fn new() -> Migrations {
+        Migrations::default()
+    }
+
+    /// Registers a step that upgrades the environment from the `from` version to the
+    /// `to` version, the steps must be registered in order: the `to` version must be
+    /// greater than the `from` one, which must be the `to` version of the previous step.
+    pub fn step(&mut self, from: u64, to: u64, apply: MigrationFn) -> &mut Self {
+        self.steps.push(Step { from, to, apply });
+        self
+    }
+
+    /// Returns the version of the environment once all the steps are applied.
+    pub fn latest_version(&self) -> u64 {
+        self.steps.last().map_or(0, |step| step.to)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut previous: Option<&Step> = None;
+
+        for step in &self.steps {
+            let msg = if step.to <= step.from {
+                Some(format!("the migration from {} to {} must upgrade the version", step.from, step.to))
+            } else if matches!(previous, Some(previous) if previous.to != step.from) {
+                Some(format!("the migration from {} doesn't follow the previous one", step.from))
+            } else {
+                None
+            };
+
+            if let Some(msg) = msg {
+                return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)));
+            }
+            previous = Some(step);
+        }
+
+        Ok(())
+    }
+
+    /// Applies the pending steps to the environment in a single write transaction,
+    /// returns the version of the environment.
+    ///
+    /// It is called when the environment is opened with [`EnvOpenOptions::migrations`].
+    ///
+    /// [`EnvOpenOptions::migrations`]: crate::EnvOpenOptions::migrations
+    pub fn apply(&self, env: &Env) -> Result<u64> {
+        self.validate()?;
+        if self.steps.is_empty() {
+            return Ok(0);
+        }
+
+        let mut wtxn = env.write_txn()?;
+        let metadata = create_metadata_database(env, &mut wtxn)?;
+        let current = read_version(&metadata, &wtxn)?;
+
+        let pending = match self.steps.iter().position(|step| step.from == current) {
+            Some(position) => &self.steps[position..],
+            None if current == self.latest_version() => &[],
+            None if current > self.latest_version() => {
+                let msg = format!("the schema version {} is newer than the migrations", current);
+                return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg)));
+            }
+            None => {
+                let msg = format!("the schema version {} is unknown to the migrations", current);
+                return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg)));
+            }
+        };
+
+        if pending.is_empty() {
+            wtxn.abort()?;
+            return Ok(current);
+        }
+
+        for step in pending {
+            (step.apply)(&mut wtxn)?;
+        }
+
+        let version = self.latest_version();
+        metadata.put(&mut wtxn, &SCHEMA_VERSION_KEY, &&version.to_be_bytes()[..])?;
+        wtxn.commit()?;
+
+        Ok(version)
+    }
+}
+
+fn read_version<T>(metadata: &MetadataDatabase, txn: &RoTxn<T>) -> Result<u64> {
+    match metadata.get(txn, &SCHEMA_VERSION_KEY)? {
+        Some(bytes) => match bytes.try_into() {
+            Ok(bytes) => Ok(u64::from_be_bytes(bytes)),
+            Err(_) => {
+                let msg = "the schema version is not a 64-bit integer";
+                Err(Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg)))
+            }
+        },
+        None => Ok(0),
+    }
+}
+
+/// Returns the schema version of the environment, zero if no migration has been applied.
+pub fn schema_version<T>(txn: &RoTxn<T>) -> Result<u64> {
+    match open_dbi(txn, Some(METADATA_DATABASE_NAME), 0)? {
+        Some(dbi) => read_version(&MetadataDatabase::new(txn.env.env_mut_ptr() as usize, dbi), txn),
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop(_wtxn: &mut RwTxn) -> Result<()> {
+        Ok(())
+    }
+
+    fn fail(_wtxn: &mut RwTxn) -> Result<()> {
+        Err(Error::Io(io::Error::new(io::ErrorKind::Other, "failed migration")))
+    }
+
+    #[test]
+    fn reject_unordered_steps() {
+        assert!(Migrations::new().step(0, 1, noop).step(1, 3, noop).validate().is_ok());
+        assert!(Migrations::new().step(1, 1, noop).validate().is_err());
+        assert!(Migrations::new().step(0, 1, noop).step(2, 3, noop).validate().is_err());
+    }
+
+    #[test]
+    fn failed_steps_are_not_applied() {
+        use std::fs;
+        use std::path::Path;
+        use crate::EnvOpenOptions;
+
+        let path = Path::new("target").join("failed-migrations.mdb");
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        let env = EnvOpenOptions::new().max_dbs(10).open(&path).unwrap();
+
+        assert_eq!(Migrations::new().step(0, 1, noop).apply(&env).unwrap(), 1);
+        assert!(Migrations::new().step(0, 1, noop).step(1, 2, fail).apply(&env).is_err());
+        assert_eq!(env.read(schema_version).unwrap(), 1);
+
+        // An environment version that no step upgrades from is rejected.
+        assert!(Migrations::new().step(5, 6, noop).apply(&env).is_err());
+        assert_eq!(Migrations::new().step(0, 1, noop).step(1, 2, noop).apply(&env).unwrap(), 2);
+
+        // An environment newer than the migrations is rejected.
+        assert!(Migrations::new().step(0, 1, noop).apply(&env).is_err());
+        assert_eq!(env.read(schema_version).unwrap(), 2);
+    }
+}
@@ -0,0 +1,167 @@
+use std::convert::TryInto;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::mem;
+use std::path::Path;
+
+/// The magic number written at the start of the meta pages of an LMDB data file.
+const MDB_MAGIC: u32 = 0xBEEF_C0DE;
+/// The version of the LMDB data file format.
+const MDB_DATA_VERSION: u32 = 1;
+/// The flag of the meta pages in the page header.
+const P_META: u16 = 0x08;
+
+const WORD: usize = mem::size_of::<usize>();
+/// The size of a page header: the page number, a padding, the flags and the bounds.
+const PAGE_HEADER: usize = WORD + 8;
+/// The size of a database record: the padding, the flags, the depth and five words.
+const DB_RECORD: usize = 8 + 5 * WORD;
+/// The offset of the page size in a meta page, stored in the padding of the free database.
+const PAGE_SIZE_OFFSET: usize = PAGE_HEADER + 8 + 2 * WORD;
+/// The offset of the last page number in a meta page, followed by the transaction id.
+const LAST_PAGE_OFFSET: usize = PAGE_HEADER + 8 + 2 * WORD + 2 * DB_RECORD;
+const META_PAGE_LEN: usize = LAST_PAGE_OFFSET + 2 * WORD;
+
+const MIN_PAGE_SIZE: usize = 512;
+const MAX_PAGE_SIZE: usize = 0x10000;
+
+/// The information of a meta page of an LMDB data file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Meta {
+    page_size: usize,
+    last_page: usize,
+    txn_id: usize,
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_word(bytes: &[u8], offset: usize) -> usize {
+    usize::from_ne_bytes(bytes[offset..offset + WORD].try_into().unwrap())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid data file: {}", msg))
+}
+
+/// Parses and validates the meta page with the given page number.
+fn parse_meta(page: &[u8], page_number: usize) -> io::Result<Meta> {
+    if read_word(page, 0) != page_number || read_u16(page, WORD + 2) & P_META == 0 {
+        return Err(invalid_data("missing meta page"));
+    }
+    if read_u32(page, PAGE_HEADER) != MDB_MAGIC {
+        return Err(invalid_data("wrong magic number"));
+    }
+    if read_u32(page, PAGE_HEADER + 4) != MDB_DATA_VERSION {
+        return Err(invalid_data("unsupported version"));
+    }
+
+    let page_size = read_u32(page, PAGE_SIZE_OFFSET) as usize;
+    if !page_size.is_power_of_two() || !(MIN_PAGE_SIZE..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(invalid_data("wrong page size"));
+    }
+
+    let last_page = read_word(page, LAST_PAGE_OFFSET);
+    let txn_id = read_word(page, LAST_PAGE_OFFSET + WORD);
+
+    Ok(Meta { page_size, last_page, txn_id })
+}
+
+/// Validates the data file read from the reader and writes it into the given file,
+/// returns the number of bytes written.
+///
+/// The two meta pages must be valid and agree on the page size, the file must be
+/// long enough to contain the last page referenced by the most recent meta page.
+fn write_data_file<R: Read>(mut reader: R, file: &mut File) -> io::Result<u64> {
+    let mut pages = vec![0; META_PAGE_LEN];
+    reader.read_exact(&mut pages).map_err(|_| invalid_data("too short"))?;
+    let first = parse_meta(&pages, 0)?;
+
+    pages.resize(first.page_size + META_PAGE_LEN, 0);
+    reader.read_exact(&mut pages[META_PAGE_LEN..]).map_err(|_| invalid_data("too short"))?;
+    let second = parse_meta(&pages[first.page_size..], 1)?;
+
+    if first.page_size != second.page_size {
+        return Err(invalid_data("the meta pages disagree on the page size"));
+    }
+    let last_page = if first.txn_id >= second.txn_id { first.last_page } else { second.last_page };
+
+    file.write_all(&pages)?;
+    let written = pages.len() as u64 + io::copy(&mut reader, file)?;
+
+    let expected = (last_page as u64 + 1) * first.page_size as u64;
+    if written < expected {
+        return Err(invalid_data("truncated"));
+    }
+
+    file.sync_all()?;
+
+    Ok(written)
+}
+
+/// Restores the data file read from the reader into the given directory, the data is
+/// validated and written into a temporary file that atomically replaces the `data.mdb` file.
+///
+/// The `lock` function is called once the temporary file is written, the guard
+/// it returns is kept while the `data.mdb` file is replaced.
+pub(crate) fn restore_data_file<R, F, G>(reader: R, target_dir: &Path, lock: F) -> io::Result<u64>
+where
+    R: Read,
+    F: FnOnce() -> io::Result<G>,
+{
+    let tmp_path = target_dir.join("data.mdb.restore");
+    let mut file = File::create(&tmp_path)?;
+
+    let result = write_data_file(reader, &mut file).and_then(|written| {
+        drop(file);
+        let _guard = lock()?;
+        fs::rename(&tmp_path, target_dir.join("data.mdb"))?;
+        Ok(written)
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(unix)]
+    fn reject_invalid_data_files() {
+        use std::fs;
+        use std::path::Path;
+        use crate::{CompactionOption, Env, EnvOpenOptions};
+        use crate::types::*;
+
+        fs::create_dir_all(Path::new("target").join("reject-invalid-data-files.mdb")).unwrap();
+        let env = EnvOpenOptions::new()
+            .map_size(10 * 1024 * 1024) // 10MB
+            .open(Path::new("target").join("reject-invalid-data-files.mdb")).unwrap();
+        let db = env.create_database::<OwnedType<u32>, Str>(None).unwrap();
+        env.write(|wtxn| (0..1000).try_for_each(|i| db.put(wtxn, &i, &"hello"))).unwrap();
+
+        let mut data = Vec::new();
+        env.copy_to_writer(&mut data, CompactionOption::Enabled).unwrap();
+        let target = Path::new("target").join("reject-invalid-data-files-restored.mdb");
+
+        // The file is truncated.
+        let truncated = &data[..data.len() / 2];
+        assert!(Env::restore_from(truncated, &target).is_err());
+
+        // The version of the second meta page is wrong.
+        let page_size = env.stat().unwrap().page_size as usize;
+        let mut wrong_version = data.clone();
+        wrong_version[page_size + super::PAGE_HEADER + 4] = 42;
+        assert!(Env::restore_from(&wrong_version[..], &target).is_err());
+
+        assert_eq!(Env::restore_from(&data[..], &target).unwrap(), data.len() as u64);
+        assert!(target.join("data.mdb").exists());
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker;
 use std::ops::Deref;
-use std::ptr;
+use std::{mem, ptr};
 
 use crate::mdb::ffi;
 use crate::mdb::error::mdb_result;
@@ -9,23 +9,63 @@ use crate::{Env, Result};
 pub struct RoTxn<'e, T=()> {
     pub(crate) txn: *mut ffi::MDB_txn,
     pub(crate) env: &'e Env,
+    /// Whether this read transaction goes back to the pool of the env when dropped.
+    pooled: bool,
     _phantom: marker::PhantomData<T>,
 }
 
 impl<'e, T> RoTxn<'e, T> {
     pub(crate) fn new(env: &'e Env) -> Result<RoTxn<'e, T>> {
-        let mut txn: *mut ffi::MDB_txn = ptr::null_mut();
-
-        unsafe {
-            mdb_result(ffi::mdb_txn_begin(
-                env.env_mut_ptr(),
-                ptr::null_mut(),
-                ffi::MDB_RDONLY,
-                &mut txn,
-            ))?
-        };
-
-        Ok(RoTxn { txn, env, _phantom: marker::PhantomData })
+        let pooled = env.has_read_txn_pool();
+
+        if let Some(txn) = env.take_pooled_read_txn() {
+            match renew_txn(env, txn) {
+                Ok(()) => return Ok(RoTxn { txn, env, pooled, _phantom: marker::PhantomData }),
+                Err(_) => { let _ = abort_txn(txn); },
+            }
+        }
+
+        let txn = begin_txn(env, ptr::null_mut(), ffi::MDB_RDONLY)?;
+        Ok(RoTxn { txn, env, pooled, _phantom: marker::PhantomData })
+    }
+
+    /// Returns the environment this transaction has been begun on.
+    pub fn env(&self) -> &'e Env {
+        self.env
+    }
+
+    /// Resets this read transaction, it releases the snapshot of the database it was reading
+    /// but keeps its reader slot, the returned handle can be cheaply renewed with
+    /// [`ResetRoTxn::renew`] to read the latest snapshot of the database.
+    ///
+    /// ```
+    /// use std::fs;
+    /// use std::path::Path;
+    /// use heed::{Database, EnvOpenOptions};
+    /// use heed::types::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// fs::create_dir_all(Path::new("target").join("reset-txn.mdb"))?;
+    /// let env = EnvOpenOptions::new()
+    ///     .map_size(10 * 1024 * 1024) // 10MB
+    ///     .open(Path::new("target").join("reset-txn.mdb"))?;
+    /// let db: Database<Str, Str> = env.create_database(None)?;
+    /// # env.write(|wtxn| db.clear(wtxn))?;
+    ///
+    /// let rtxn = env.read_txn()?;
+    /// assert_eq!(db.get(&rtxn, &"hello")?, None);
+    /// let parked = rtxn.reset();
+    ///
+    /// env.write(|wtxn| db.put(wtxn, &"hello", &"world"))?;
+    ///
+    /// let rtxn = parked.renew()?;
+    /// assert_eq!(db.get(&rtxn, &"hello")?, Some("world"));
+    /// # Ok(()) }
+    /// ```
+    pub fn reset(mut self) -> ResetRoTxn<'e, T> {
+        let txn = mem::replace(&mut self.txn, ptr::null_mut());
+        unsafe { ffi::mdb_txn_reset(txn) };
+        ResetRoTxn { txn, env: self.env, pooled: self.pooled, _phantom: marker::PhantomData }
     }
 
     pub fn commit(mut self) -> Result<()> {
@@ -44,7 +84,81 @@ impl<'e, T> RoTxn<'e, T> {
 impl<T> Drop for RoTxn<'_, T> {
     fn drop(&mut self) {
         if !self.txn.is_null() {
-            let _ = abort_txn(self.txn);
+            if self.pooled {
+                unsafe { ffi::mdb_txn_reset(self.txn) };
+                if !self.env.give_back_pooled_read_txn(self.txn) {
+                    let _ = abort_txn(self.txn);
+                }
+            } else {
+                let _ = abort_txn(self.txn);
+            }
+        }
+        self.env.txn_ended();
+    }
+}
+
+/// A read transaction that has been reset by [`RoTxn::reset`], it doesn't
+/// read any snapshot of the database until it is renewed.
+///
+/// A transaction taken from the read transaction pool of the env goes back
+/// to it when dropped, whether it has been renewed or not.
+pub struct ResetRoTxn<'e, T=()> {
+    txn: *mut ffi::MDB_txn,
+    env: &'e Env,
+    /// Whether the renewed transaction goes back to the pool of the env when dropped.
+    pooled: bool,
+    _phantom: marker::PhantomData<T>,
+}
+
+impl<'e, T> ResetRoTxn<'e, T> {
+    /// Renews this read transaction, it reads the latest snapshot of the database.
+    pub fn renew(mut self) -> Result<RoTxn<'e, T>> {
+        let txn = mem::replace(&mut self.txn, ptr::null_mut());
+
+        match renew_txn(self.env, txn) {
+            Ok(()) => Ok(RoTxn { txn, env: self.env, pooled: self.pooled, _phantom: marker::PhantomData }),
+            Err(e) => {
+                let _ = abort_txn(txn);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<T> Drop for ResetRoTxn<'_, T> {
+    fn drop(&mut self) {
+        if !self.txn.is_null() {
+            // The transaction is already reset, it can go back to the pool as is.
+            if !self.pooled || !self.env.give_back_pooled_read_txn(self.txn) {
+                let _ = abort_txn(self.txn);
+            }
+        }
+    }
+}
+
+/// Renews a reset read transaction, it is counted as active like a new transaction.
+fn renew_txn(env: &Env, txn: *mut ffi::MDB_txn) -> Result<()> {
+    env.txn_started();
+    let result = unsafe { mdb_result(ffi::mdb_txn_renew(txn)) };
+
+    result.map_err(|e| {
+        env.txn_ended();
+        e.into()
+    })
+}
+
+/// Begins a new transaction, it is counted as active until the `RoTxn` that wraps it is dropped.
+fn begin_txn(env: &Env, parent: *mut ffi::MDB_txn, flags: u32) -> Result<*mut ffi::MDB_txn> {
+    let mut txn: *mut ffi::MDB_txn = ptr::null_mut();
+
+    env.txn_started();
+    let result = unsafe { mdb_result(ffi::mdb_txn_begin(env.env_mut_ptr(), parent, flags, &mut txn)) };
+
+    match result {
+        Ok(()) => Ok(txn),
+        Err(e) => {
+            env.txn_ended();
+            Err(e.into())
         }
     }
 }
@@ -56,7 +170,8 @@ unsafe impl<T> Sync for RoTxn<'_, T> { }
 fn abort_txn(txn: *mut ffi::MDB_txn) -> Result<()> {
     // Asserts that the transaction hasn't been already committed.
     assert!(!txn.is_null());
-    Ok(unsafe { ffi::mdb_txn_abort(txn) })
+    unsafe { ffi::mdb_txn_abort(txn) };
+    Ok(())
 }
 
 #[cfg(all(feature = "mdbx", not(feature = "lmdb")))]
@@ -75,44 +190,29 @@ pub struct RwTxn<'e, 'p, T=()> {
 
 impl<'e, T> RwTxn<'e, 'e, T> {
     pub(crate) fn new(env: &'e Env) -> Result<RwTxn<'e, 'e, T>> {
-        let mut txn: *mut ffi::MDB_txn = ptr::null_mut();
-
-        unsafe {
-            mdb_result(ffi::mdb_txn_begin(
-                env.env_mut_ptr(),
-                ptr::null_mut(),
-                0,
-                &mut txn,
-            ))?
-        };
+        let txn = begin_txn(env, ptr::null_mut(), 0)?;
 
         Ok(RwTxn {
-            txn: RoTxn { txn, env, _phantom: marker::PhantomData },
+            txn: RoTxn { txn, env, pooled: false, _phantom: marker::PhantomData },
             _parent: marker::PhantomData,
         })
     }
 
     pub(crate) fn nested<'p: 'e>(env: &'e Env, parent: &'p mut RwTxn<T>) -> Result<RwTxn<'e, 'p, T>> {
-        let mut txn: *mut ffi::MDB_txn = ptr::null_mut();
         let parent_ptr: *mut ffi::MDB_txn = parent.txn.txn;
-
-        unsafe {
-            mdb_result(ffi::mdb_txn_begin(
-                env.env_mut_ptr(),
-                parent_ptr,
-                0,
-                &mut txn,
-            ))?
-        };
+        let txn = begin_txn(env, parent_ptr, 0)?;
 
         Ok(RwTxn {
-            txn: RoTxn { txn, env, _phantom: marker::PhantomData },
+            txn: RoTxn { txn, env, pooled: false, _phantom: marker::PhantomData },
             _parent: marker::PhantomData,
         })
     }
 
     pub fn commit(self) -> Result<()> {
-        self.txn.commit()
+        let env = self.txn.env;
+        self.txn.commit()?;
+        env.write_txn_committed();
+        Ok(())
     }
 
     pub fn abort(self) -> Result<()> {